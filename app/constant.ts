@@ -1,12 +1,12 @@
 import bs58 from 'bs58'
-import { BN, BorshAccountsCoder, web3 } from '@project-serum/anchor'
+import { BN, BorshAccountsCoder, web3 } from '@coral-xyz/anchor'
 
 import { IDL } from '../target/types/inter_dao'
-import { FeeOptions } from './types'
+import { FeeOptions, ProposalBounds } from './types'
 
 export const DEFAULT_RPC_ENDPOINT = 'https://api.devnet.solana.com'
 export const DEFAULT_INTERDAO_PROGRAM_ID =
-  'DaoMWqkUbZd1amaqxoxqCBveRbdcVZbVFNqa22EwCfMi'
+  'xWfewaindgnhppdM9qpAhoDpWrdm1myRmgsPLPhGEuP'
 export const DEFAULT_INTERDAO_IDL = IDL
 
 export const DAO_DISCRIMINATOR = bs58.encode(
@@ -19,6 +19,8 @@ export const RECEIPT_DISCRIMINATOR = bs58.encode(
   BorshAccountsCoder.accountDiscriminator('receipt'),
 )
 
+export const MAX_METADATA_URI_SIZE = 200
+
 export const FEE_OPTIONS = (
   walletAddress: string = new web3.Keypair().publicKey.toBase58(),
 ): FeeOptions => ({
@@ -27,3 +29,13 @@ export const FEE_OPTIONS = (
   revenue: new BN(0),
   revenuemanAddress: walletAddress,
 })
+
+// Executors always get 10 minutes to retry before an instruction can be failed
+export const MIN_RETRY_WINDOW = new BN(600)
+export const PROPOSAL_BOUNDS: ProposalBounds = {
+  minVotingDuration: new BN(86400), // One day
+  maxVotingDuration: new BN(7776000), // One quarter
+  minNoticePeriod: new BN(0),
+  executionWindow: new BN(0), // Never expires
+  retryWindow: MIN_RETRY_WINDOW,
+}
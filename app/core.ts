@@ -1,6 +1,6 @@
 // @ts-ignore
 import * as soproxABI from 'soprox-abi'
-import { web3, Program, utils, BN, AnchorProvider } from '@project-serum/anchor'
+import { web3, Program, utils, BN, AnchorProvider } from '@coral-xyz/anchor'
 
 import { InterDao } from './../target/types/inter_dao'
 
@@ -9,6 +9,9 @@ import {
   DEFAULT_INTERDAO_PROGRAM_ID,
   DEFAULT_INTERDAO_IDL,
   FEE_OPTIONS,
+  MAX_METADATA_URI_SIZE,
+  MIN_RETRY_WINDOW,
+  PROPOSAL_BOUNDS,
 } from './constant'
 import {
   AnchorWallet,
@@ -20,16 +23,23 @@ import {
   DaoRegime,
   DaoRegimes,
   FeeOptions,
+  HashAlgorithm,
+  HashAlgorithms,
   IdlEvents,
   InvokedAccount,
   ContentData,
+  ProposalBounds,
   ProposalData,
+  ProposalStatus,
   ReceiptData,
+  VoterRecordData,
 } from './types'
 import {
+  findContent,
   findNftMetadataAddress,
   findProposal,
   findReceipt,
+  findVoterRecord,
   isAddress,
 } from './utils'
 
@@ -161,6 +171,21 @@ class InterDAO {
     return this.program.account.proposal.fetch(proposalAddress) as any
   }
 
+  /**
+   * Get the proposal status evaluated by the program at the current time.
+   * @param proposalAddress Proposal address.
+   * @returns Proposal status.
+   */
+  getProposalStatus = async (
+    proposalAddress: string,
+  ): Promise<ProposalStatus> => {
+    const { dao } = await this.getProposalData(proposalAddress)
+    return this.program.methods
+      .getProposalStatus()
+      .accounts({ proposal: new web3.PublicKey(proposalAddress), dao })
+      .view()
+  }
+
   /**
    * Parse receipt buffer data.
    * @param data Receipt buffer data.
@@ -273,29 +298,45 @@ class InterDAO {
   }
 
   /**
-   * Find an available receipt's index of a wallet for a proposal
-   * @param proposalAddress Proposal address
-   * @param authorityAddress Wallet address
-   * @returns
+   * Derive my voter record address on a proposal.
+   * @param proposalAddress Proposal address.
+   * @returns Voter record address.
    */
-  findAvailableReceiptIndex = async (
-    proposalAddress: string,
-    authorityAddress: string,
-  ) => {
+  deriveVoterRecordAddress = async (proposalAddress: string) => {
     if (!isAddress(proposalAddress)) throw new Error('Invalid proposal address')
-    if (!isAddress(authorityAddress))
-      throw new Error('Invalid authority address')
+    const voterRecordPublicKey = await findVoterRecord(
+      new web3.PublicKey(proposalAddress),
+      this._provider.wallet.publicKey,
+      this.program.programId,
+    )
+    return voterRecordPublicKey.toBase58()
+  }
 
-    let receiptIndex = new BN(0)
-    const one = new BN(1)
-    while (true) {
-      try {
-        await this.deriveReceiptAddress(receiptIndex, proposalAddress, true)
-        receiptIndex = receiptIndex.add(one)
-      } catch (er: any) {
-        return receiptIndex
-      }
-    }
+  /**
+   * Get voter record data.
+   * @param voterRecordAddress Voter record address.
+   * @returns Voter record readable data.
+   */
+  getVoterRecordData = async (
+    voterRecordAddress: string,
+  ): Promise<VoterRecordData> => {
+    return this.program.account.voterRecord.fetch(voterRecordAddress) as any
+  }
+
+  /**
+   * Get the index the program assigns to my next receipt on a proposal.
+   * Receipts are indexed from zero in the voting order, so the previous ones are below it.
+   * @param proposalAddress Proposal address.
+   * @returns Receipt index.
+   */
+  getNextReceiptIndex = async (proposalAddress: string) => {
+    const voterRecordAddress = await this.deriveVoterRecordAddress(
+      proposalAddress,
+    )
+    const voterRecord = await this.program.account.voterRecord.fetchNullable(
+      voterRecordAddress,
+    )
+    return voterRecord ? voterRecord.receipts : new BN(0)
   }
 
   /**
@@ -336,6 +377,9 @@ class InterDAO {
    * @param regime (Optional) DAO regime. Default is Dictatorial.
    * @param isNftVoting (Optional) If isNftVoting equals to "true", tokenAddress will be collection address of NFT.
    * @param isPublic (Optional) If isPublic equals to "true", it will be public DAO.
+   * @param bounds (Optional) Proposal duration bounds in seconds. Default is PROPOSAL_BOUNDS.
+   * @param hashAlgorithm (Optional) The algorithm of the metadata hash. Default is Sha256.
+   * @param metadataUri (Optional) The ipfs://, ar:// or https:// URI of the metadata.
    * @returns { txId, daoAddress }
    */
   initializeDao = async (
@@ -346,11 +390,37 @@ class InterDAO {
     regime: DaoRegime = DaoRegimes.Dictatorial,
     isNftVoting: Boolean = false,
     isPublic: Boolean = true,
+    bounds: Partial<ProposalBounds> = {},
+    hashAlgorithm: HashAlgorithm = HashAlgorithms.Sha256,
+    metadataUri: string = '',
   ) => {
+    const {
+      minVotingDuration,
+      maxVotingDuration,
+      minNoticePeriod,
+      executionWindow,
+      retryWindow,
+    } = {
+      ...PROPOSAL_BOUNDS,
+      ...bounds,
+    }
     if (!isAddress(tokenAddress)) throw new Error('Invalid token address')
     if (!tokenSupply.gt(new BN(0)))
       throw new Error('Invalid token supply must be greater than zero')
     if (metadata.length !== 32) throw new Error('Invalid metadata path')
+    if (Buffer.byteLength(metadataUri) > MAX_METADATA_URI_SIZE)
+      throw new Error('Invalid metadata uri')
+    if (!minVotingDuration.gt(new BN(0)))
+      throw new Error('Invalid min voting duration')
+    if (maxVotingDuration.lt(minVotingDuration))
+      throw new Error('Invalid max voting duration')
+    if (minNoticePeriod.isNeg()) throw new Error('Invalid min notice period')
+    if (executionWindow.isNeg()) throw new Error('Invalid execution window')
+    if (
+      retryWindow.lt(MIN_RETRY_WINDOW) ||
+      (!executionWindow.isZero() && retryWindow.gte(executionWindow))
+    )
+      throw new Error('Invalid retry window')
     const masterAddress = await this.deriveMasterAddress(
       dao.publicKey.toBase58(),
     )
@@ -360,8 +430,15 @@ class InterDAO {
       regime,
       tokenSupply,
       metadata,
+      hashAlgorithm,
+      metadataUri,
       isNftVoting,
       isPublic,
+      minVotingDuration,
+      maxVotingDuration,
+      minNoticePeriod,
+      executionWindow,
+      retryWindow,
       {
         accounts: {
           dao: dao.publicKey,
@@ -383,6 +460,9 @@ class InterDAO {
    * @param startDate Start voting
    * @param endDate End voting
    * @param consensusMechanism (Optional) Consensus mechanism. Default is StakedTokenCounter.
+   * @param hashAlgorithm (Optional) The algorithm of the metadata hash. Default is Sha256.
+   * @param metadataUri (Optional) The ipfs://, ar:// or https:// URI of the metadata.
+   * @param independentInstructions (Optional) If true, failed instructions are skipped instead of halting the execution.
    * @returns { txId, proposalAddress }
    */
   initializeProposal = async ({
@@ -392,6 +472,9 @@ class InterDAO {
     metadata,
     consensusMechanism = ConsensusMechanisms.StakedTokenCounter,
     consensusQuorum = ConsensusQuorums.Half,
+    hashAlgorithm = HashAlgorithms.Sha256,
+    metadataUri = '',
+    independentInstructions = false,
     feeOptions = {},
     sendAndConfirm = true,
   }: {
@@ -401,6 +484,9 @@ class InterDAO {
     metadata: Buffer | Uint8Array | number[]
     consensusMechanism: ConsensusMechanism
     consensusQuorum: ConsensusQuorum
+    hashAlgorithm?: HashAlgorithm
+    metadataUri?: string
+    independentInstructions?: boolean
     feeOptions?: Partial<FeeOptions>
     sendAndConfirm?: boolean
   }) => {
@@ -413,6 +499,8 @@ class InterDAO {
     if (!isAddress(revenuemanAddress))
       throw new Error('Invalid revenue receiver address')
     if (metadata.length !== 32) throw new Error('Invalid metadata path')
+    if (Buffer.byteLength(metadataUri) > MAX_METADATA_URI_SIZE)
+      throw new Error('Invalid metadata uri')
 
     const currentTime = await this.getCurrentUnixTimestamp()
     if (startDate <= currentTime) throw new Error('Invalid start date')
@@ -433,6 +521,9 @@ class InterDAO {
         new BN(startDate),
         new BN(endDate),
         Array.from(metadata),
+        hashAlgorithm,
+        metadataUri,
+        independentInstructions,
         tax,
         revenue,
       )
@@ -554,7 +645,7 @@ class InterDAO {
   }) => {
     if (!isAddress(proposal)) throw new Error('Invalid proposal address')
 
-    const { dao, totalExecuted, totalInstruction } =
+    const { dao, totalExecuted, totalFailed, totalInstruction } =
       await this.program.account.proposal.fetch(proposal)
     const proposalIxs = await this.program.account.proposalInstruction.all([
       { memcmp: { offset: 8, bytes: proposal } },
@@ -562,7 +653,8 @@ class InterDAO {
     const tx = new web3.Transaction()
     const masterPublicKey = await this.deriveMasterAddress(dao.toBase58())
 
-    for (let i = totalExecuted; i < totalInstruction; i++) {
+    // Failed instructions are never executed
+    for (let i = totalExecuted + totalFailed; i < totalInstruction; i++) {
       const proposalIx = proposalIxs.find((ix) => ix.account.index === i)!
       const { accounts, invokedProgram, proposal } = proposalIx.account
       const remainingAccounts = (accounts as InvokedAccount[]).map(
@@ -595,6 +687,60 @@ class InterDAO {
     return { txId }
   }
 
+  /**
+   * Mark the next proposal instruction as failed once its retry window is over.
+   * @param proposalAddress Proposal address.
+   * @returns { txId, tx }
+   */
+  failProposalInstruction = async (
+    proposalAddress: string,
+    sendAndConfirm = true,
+  ) => {
+    if (!isAddress(proposalAddress)) throw new Error('Invalid proposal address')
+    const { dao, totalExecuted, totalFailed } =
+      await this.program.account.proposal.fetch(proposalAddress)
+    const proposalIxs = await this.program.account.proposalInstruction.all([
+      { memcmp: { offset: 8, bytes: proposalAddress } },
+    ])
+    const proposalIx = proposalIxs.find(
+      ({ account: { index } }) => index === totalExecuted + totalFailed,
+    )
+    if (!proposalIx) throw new Error('No proposal instruction left')
+    let txId = ''
+    const tx = await this.program.methods
+      .failProposalInstruction()
+      .accounts({
+        caller: this._provider.wallet.publicKey,
+        proposal: new web3.PublicKey(proposalAddress),
+        proposalInstruction: proposalIx.publicKey,
+        dao,
+      })
+      .transaction()
+    if (sendAndConfirm) txId = await this._provider.sendAndConfirm(tx)
+    return { txId, tx }
+  }
+
+  /**
+   * Record the outcome of an ended proposal.
+   * @param proposalAddress Proposal address.
+   * @returns { txId, tx }
+   */
+  finalizeProposal = async (proposalAddress: string, sendAndConfirm = true) => {
+    if (!isAddress(proposalAddress)) throw new Error('Invalid proposal address')
+    const { dao: daoPublicKey } = await this.getProposalData(proposalAddress)
+    let txId = ''
+    const tx = await this.program.methods
+      .finalizeProposal()
+      .accounts({
+        caller: this._provider.wallet.publicKey,
+        proposal: new web3.PublicKey(proposalAddress),
+        dao: daoPublicKey,
+      })
+      .transaction()
+    if (sendAndConfirm) txId = await this._provider.sendAndConfirm(tx)
+    return { txId, tx }
+  }
+
   /**
    * Vote for a proposal.
    * @param proposalAddress Proposal address.
@@ -627,15 +773,16 @@ class InterDAO {
       mint: mintPublicKey,
       owner: authorityPublicKey,
     })
-    const index = await this.findAvailableReceiptIndex(
-      proposalAddress,
-      authorityPublicKey.toBase58(),
-    )
+    const index = await this.getNextReceiptIndex(proposalAddress)
     const receiptAddress = await this.deriveReceiptAddress(
       index,
       proposalAddress,
     )
     const receiptPublicKey = new web3.PublicKey(receiptAddress)
+    const voterRecordAddress = await this.deriveVoterRecordAddress(
+      proposalAddress,
+    )
+    const voterRecordPublicKey = new web3.PublicKey(voterRecordAddress)
     const treasurerAddress = await this.deriveTreasurerAddress(proposalAddress)
     const treasurerPublicKey = new web3.PublicKey(treasurerAddress)
     const treasuryPublicKey = await utils.token.associatedAddress({
@@ -647,7 +794,7 @@ class InterDAO {
 
     let txId = ''
     const tx = await this.program.methods
-      .voteFor(index, amount, tax, revenue)
+      .voteFor(amount, tax, revenue)
       .accounts({
         authority: authorityPublicKey,
         src: srcPublicKey,
@@ -657,6 +804,7 @@ class InterDAO {
         proposal: proposalPublicKey,
         dao: daoPublicKey,
         receipt: receiptPublicKey,
+        voterRecord: voterRecordPublicKey,
         taxman: taxmanPublicKey,
         revenueman: revenuemanPublicKey,
         tokenProgram: utils.token.TOKEN_PROGRAM_ID,
@@ -701,15 +849,16 @@ class InterDAO {
       mint: nftPublicKey,
       owner: authorityPublicKey,
     })
-    const index = await this.findAvailableReceiptIndex(
-      proposalAddress,
-      authorityPublicKey.toBase58(),
-    )
+    const index = await this.getNextReceiptIndex(proposalAddress)
     const receiptAddress = await this.deriveReceiptAddress(
       index,
       proposalAddress,
     )
     const receiptPublicKey = new web3.PublicKey(receiptAddress)
+    const voterRecordAddress = await this.deriveVoterRecordAddress(
+      proposalAddress,
+    )
+    const voterRecordPublicKey = new web3.PublicKey(voterRecordAddress)
     const treasurerAddress = await this.deriveTreasurerAddress(proposalAddress)
     const treasurerPublicKey = new web3.PublicKey(treasurerAddress)
     const treasuryPublicKey = await utils.token.associatedAddress({
@@ -721,7 +870,7 @@ class InterDAO {
 
     let txId = ''
     const tx = await this.program.methods
-      .voteNftFor(index, tax, revenue)
+      .voteNftFor(tax, revenue)
       .accounts({
         authority: authorityPublicKey,
         src: srcPublicKey,
@@ -732,6 +881,7 @@ class InterDAO {
         proposal: proposalPublicKey,
         dao: daoPublicKey,
         receipt: receiptPublicKey,
+        voterRecord: voterRecordPublicKey,
         taxman: taxmanPublicKey,
         revenueman: revenuemanPublicKey,
         tokenProgram: utils.token.TOKEN_PROGRAM_ID,
@@ -777,15 +927,16 @@ class InterDAO {
       mint: mintPublicKey,
       owner: authorityPublicKey,
     })
-    const index = await this.findAvailableReceiptIndex(
-      proposalAddress,
-      authorityPublicKey.toBase58(),
-    )
+    const index = await this.getNextReceiptIndex(proposalAddress)
     const receiptAddress = await this.deriveReceiptAddress(
       index,
       proposalAddress,
     )
     const receiptPublicKey = new web3.PublicKey(receiptAddress)
+    const voterRecordAddress = await this.deriveVoterRecordAddress(
+      proposalAddress,
+    )
+    const voterRecordPublicKey = new web3.PublicKey(voterRecordAddress)
     const treasurerAddress = await this.deriveTreasurerAddress(proposalAddress)
     const treasurerPublicKey = new web3.PublicKey(treasurerAddress)
     const treasuryPublicKey = await utils.token.associatedAddress({
@@ -797,7 +948,7 @@ class InterDAO {
 
     let txId = ''
     const tx = await this.program.methods
-      .voteAgainst(index, amount, tax, revenue)
+      .voteAgainst(amount, tax, revenue)
       .accounts({
         authority: authorityPublicKey,
         src: srcPublicKey,
@@ -807,6 +958,7 @@ class InterDAO {
         proposal: proposalPublicKey,
         dao: daoPublicKey,
         receipt: receiptPublicKey,
+        voterRecord: voterRecordPublicKey,
         taxman: taxmanPublicKey,
         revenueman: revenuemanPublicKey,
         tokenProgram: utils.token.TOKEN_PROGRAM_ID,
@@ -853,15 +1005,16 @@ class InterDAO {
       mint: nftPublicKey,
       owner: authorityPublicKey,
     })
-    const index = await this.findAvailableReceiptIndex(
-      proposalAddress,
-      authorityPublicKey.toBase58(),
-    )
+    const index = await this.getNextReceiptIndex(proposalAddress)
     const receiptAddress = await this.deriveReceiptAddress(
       index,
       proposalAddress,
     )
     const receiptPublicKey = new web3.PublicKey(receiptAddress)
+    const voterRecordAddress = await this.deriveVoterRecordAddress(
+      proposalAddress,
+    )
+    const voterRecordPublicKey = new web3.PublicKey(voterRecordAddress)
     const treasurerAddress = await this.deriveTreasurerAddress(proposalAddress)
     const treasurerPublicKey = new web3.PublicKey(treasurerAddress)
     const treasuryPublicKey = await utils.token.associatedAddress({
@@ -873,7 +1026,7 @@ class InterDAO {
 
     let txId = ''
     let tx = await this.program.methods
-      .voteNftAgainst(index, tax, revenue)
+      .voteNftAgainst(tax, revenue)
       .accounts({
         authority: authorityPublicKey,
         src: srcPublicKey,
@@ -884,6 +1037,7 @@ class InterDAO {
         proposal: proposalPublicKey,
         dao: daoPublicKey,
         receipt: receiptPublicKey,
+        voterRecord: voterRecordPublicKey,
         taxman: taxmanPublicKey,
         revenueman: revenuemanPublicKey,
         tokenProgram: utils.token.TOKEN_PROGRAM_ID,
@@ -909,9 +1063,7 @@ class InterDAO {
       receiptAddress,
     )
     const proposalAddress = proposalPublicKey.toBase58()
-    const { dao: daoPublicKey, endDate } = await this.getProposalData(
-      proposalAddress,
-    )
+    const { dao: daoPublicKey } = await this.getProposalData(proposalAddress)
     const { mint: mintPublicKey } = await this.getDaoData(
       daoPublicKey.toBase58(),
     )
@@ -928,8 +1080,9 @@ class InterDAO {
       owner: treasurerPublicKey,
     })
 
-    const currentTime = await this.getCurrentUnixTimestamp()
-    if (currentTime <= endDate.toNumber())
+    // Proposals decided early can be closed before their end date
+    const { state } = await this.getProposalStatus(proposalAddress)
+    if ('draft' in state || 'voting' in state)
       throw new Error('The proposal is not ended yet')
 
     let txId = ''
@@ -967,9 +1120,7 @@ class InterDAO {
     const { proposal: proposalPublicKey, mint: nftPublicKey } =
       await this.getReceiptData(receiptAddress)
     const proposalAddress = proposalPublicKey.toBase58()
-    const { dao: daoPublicKey, endDate } = await this.getProposalData(
-      proposalAddress,
-    )
+    const { dao: daoPublicKey } = await this.getProposalData(proposalAddress)
 
     const metadataAddress = await findNftMetadataAddress(nftPublicKey)
     const metadataPublicKey = metadataAddress.toBase58()
@@ -985,8 +1136,9 @@ class InterDAO {
       mint: nftPublicKey,
       owner: treasurerPublicKey,
     })
-    const currentTime = await this.getCurrentUnixTimestamp()
-    if (currentTime <= endDate.toNumber())
+    // Proposals decided early can be closed before their end date
+    const { state } = await this.getProposalStatus(proposalAddress)
+    if ('draft' in state || 'voting' in state)
       throw new Error('The proposal is not ended yet')
 
     let txId = ''
@@ -1048,23 +1200,32 @@ class InterDAO {
   }
 
   /**
-   * Update DAO's metadata
+   * Update DAO's metadata. The DAO account is resized to the URI at the wallet's expense.
    * @param metadata The new metadata.
    * @param daoAddress DAO address.
+   * @param hashAlgorithm (Optional) The algorithm of the metadata hash. Default is Sha256.
+   * @param metadataUri (Optional) The ipfs://, ar:// or https:// URI of the metadata.
    * @returns { txId }
    */
   updateDaoMetadata = async (
     metadata: Buffer | Uint8Array | number[],
     daoAddress: string,
+    hashAlgorithm: HashAlgorithm = HashAlgorithms.Sha256,
+    metadataUri: string = '',
   ) => {
     if (!isAddress(daoAddress)) throw new Error('Invalid DAO address')
     if (metadata.length !== 32) throw new Error('Invalid metadata path')
+    if (Buffer.byteLength(metadataUri) > MAX_METADATA_URI_SIZE)
+      throw new Error('Invalid metadata uri')
     const txId = await this.program.rpc.updateDaoMetadata(
       metadata as number[],
+      hashAlgorithm,
+      metadataUri,
       {
         accounts: {
           authority: this._provider.wallet.publicKey,
           dao: new web3.PublicKey(daoAddress),
+          systemProgram: web3.SystemProgram.programId,
         },
       },
     )
@@ -1072,8 +1233,45 @@ class InterDAO {
   }
 
   /**
-   * Transfer the DAO authority
-   * @param newAuthority The new taxman authority (the function will auto derive the taxman account for the authority).
+   * Update the proposal duration bounds of a DAO
+   * @param bounds The new bounds in seconds. Missing ones are kept.
+   * @param daoAddress DAO address.
+   * @returns { txId }
+   */
+  updateProposalBounds = async (
+    bounds: Partial<ProposalBounds>,
+    daoAddress: string,
+  ) => {
+    if (!isAddress(daoAddress)) throw new Error('Invalid DAO address')
+    const {
+      minVotingDuration,
+      maxVotingDuration,
+      minNoticePeriod,
+      executionWindow,
+      retryWindow,
+    } = {
+      ...(await this.getDaoData(daoAddress)),
+      ...bounds,
+    }
+    const txId = await this.program.rpc.updateProposalBounds(
+      minVotingDuration,
+      maxVotingDuration,
+      minNoticePeriod,
+      executionWindow,
+      retryWindow,
+      {
+        accounts: {
+          authority: this._provider.wallet.publicKey,
+          dao: new web3.PublicKey(daoAddress),
+        },
+      },
+    )
+    return { txId }
+  }
+
+  /**
+   * Transfer the DAO authority. The new authority takes over once it accepts.
+   * @param newAuthority The new authority address.
    * @param daoAddress DAO address.
    * @returns { txId }
    */
@@ -1090,39 +1288,121 @@ class InterDAO {
     return { txId }
   }
 
-  deriveContentAddress = async (discriminator: Buffer | Uint8Array) => {
-    const [content] = await web3.PublicKey.findProgramAddress(
-      [
-        Buffer.from('content'),
-        discriminator,
-        this._provider.wallet.publicKey.toBuffer(),
-      ],
+  /**
+   * Accept a pending authority transfer. The wallet must be the new authority.
+   * @param daoAddress DAO address.
+   * @returns { txId }
+   */
+  acceptAuthority = async (daoAddress: string) => {
+    if (!isAddress(daoAddress)) throw new Error('Invalid DAO address')
+    const txId = await this.program.rpc.acceptAuthority({
+      accounts: {
+        newAuthority: this._provider.wallet.publicKey,
+        dao: new web3.PublicKey(daoAddress),
+      },
+    })
+    return { txId }
+  }
+
+  /**
+   * Cancel a pending authority transfer
+   * @param daoAddress DAO address.
+   * @returns { txId }
+   */
+  cancelAuthorityTransfer = async (daoAddress: string) => {
+    if (!isAddress(daoAddress)) throw new Error('Invalid DAO address')
+    const txId = await this.program.rpc.cancelAuthorityTransfer({
+      accounts: {
+        authority: this._provider.wallet.publicKey,
+        dao: new web3.PublicKey(daoAddress),
+      },
+    })
+    return { txId }
+  }
+
+  /**
+   * Derive a content address by the dao address and its content nonce.
+   * @param daoAddress DAO address.
+   * @param nonce Content nonce of the DAO.
+   * @returns Content address.
+   */
+  deriveContentAddress = async (daoAddress: string, nonce: BN) => {
+    if (!isAddress(daoAddress)) throw new Error('Invalid DAO address')
+    const contentPublicKey = await findContent(
+      nonce,
+      new web3.PublicKey(daoAddress),
       this.program.programId,
     )
-    return content
+    return contentPublicKey.toBase58()
   }
 
+  /**
+   * Post a content to a DAO, or to a proposal discussion.
+   * Holdings are only checked when posting, edits stand even if the author sold the tokens.
+   * @param daoAddress DAO address.
+   * @param discriminator Content tag.
+   * @param metadata Content metadata.
+   * @param hashAlgorithm (Optional) The algorithm of the metadata hash. Default is Sha256.
+   * @param metadataUri (Optional) The ipfs://, ar:// or https:// URI of the metadata.
+   * @param proposalAddress (Optional) Discussed proposal.
+   * @param parentAddress (Optional) Replied content.
+   * @returns { txId, contentAddress }
+   */
   initializeContent = async (
+    daoAddress: string,
     discriminator: Buffer | Uint8Array,
     metadata: Buffer | Uint8Array,
+    hashAlgorithm: HashAlgorithm = HashAlgorithms.Sha256,
+    metadataUri: string = '',
+    proposalAddress?: string,
+    parentAddress?: string,
     sendAndConfirm = true,
   ) => {
+    if (!isAddress(daoAddress)) throw new Error('Invalid DAO address')
     if (discriminator.length !== 8)
       throw new Error('Invalid discriminator path')
     if (metadata.length !== 32) throw new Error('Invalid metadata path')
-
-    const content = await this.deriveContentAddress(discriminator)
+    if (Buffer.byteLength(metadataUri) > MAX_METADATA_URI_SIZE)
+      throw new Error('Invalid metadata uri')
+    if (proposalAddress && !isAddress(proposalAddress))
+      throw new Error('Invalid proposal address')
+    if (parentAddress && !isAddress(parentAddress))
+      throw new Error('Invalid parent address')
+
+    const { contentNonce, contentThreshold, mint } = await this.getDaoData(
+      daoAddress,
+    )
+    const contentAddress = await this.deriveContentAddress(
+      daoAddress,
+      contentNonce,
+    )
+    const authorityPublicKey = this._provider.wallet.publicKey
+    const holdings = contentThreshold.isZero()
+      ? null
+      : await utils.token.associatedAddress({
+          mint,
+          owner: authorityPublicKey,
+        })
     let txId = ''
     const tx = await this.program.methods
-      .initializeContent(Array.from(discriminator), Array.from(metadata))
+      .initializeContent(
+        Array.from(discriminator),
+        Array.from(metadata),
+        hashAlgorithm,
+        metadataUri,
+      )
       .accounts({
-        authority: this._provider.wallet.publicKey,
-        content,
+        authority: authorityPublicKey,
+        content: new web3.PublicKey(contentAddress),
+        dao: new web3.PublicKey(daoAddress),
+        proposal: proposalAddress ? new web3.PublicKey(proposalAddress) : null,
+        parent: parentAddress ? new web3.PublicKey(parentAddress) : null,
+        holdings,
         systemProgram: web3.SystemProgram.programId,
       })
       .transaction()
     if (sendAndConfirm) txId = await this._provider.sendAndConfirm(tx)
-    return { txId, tx }
+    return { txId, contentAddress, tx }
   }
 }
 
@@ -1,15 +1,17 @@
-import { IdlAccounts, IdlTypes, Idl, BN } from '@project-serum/anchor'
-import { IdlEvent } from '@project-serum/anchor/dist/cjs/idl'
-import { TypeDef } from '@project-serum/anchor/dist/cjs/program/namespace/types'
-import { Wallet } from '@project-serum/anchor/dist/cjs/provider'
+import { IdlAccounts, IdlTypes, Idl, BN } from '@coral-xyz/anchor'
+import { IdlEvent } from '@coral-xyz/anchor/dist/cjs/idl'
+import { TypeDef } from '@coral-xyz/anchor/dist/cjs/program/namespace/types'
+import { Wallet } from '@coral-xyz/anchor/dist/cjs/provider'
 import { InterDao } from '../target/types/inter_dao'
 
 export type AnchorWallet = Wallet
 
 export type DaoData = IdlAccounts<InterDao>['dao']
 export type ProposalData = IdlAccounts<InterDao>['proposal']
+export type ProposalStatus = IdlTypes<InterDao>['ProposalStatus']
 export type ReceiptData = IdlAccounts<InterDao>['receipt']
 export type ContentData = IdlAccounts<InterDao>['content']
+export type VoterRecordData = IdlAccounts<InterDao>['voterRecord']
 
 export type DaoRegime = IdlTypes<InterDao>['DaoRegime']
 export const DaoRegimes: Record<string, DaoRegime> = {
@@ -31,6 +33,26 @@ export const ConsensusQuorums: Record<string, ConsensusQuorum> = {
   TwoThird: { twoThird: {} },
 }
 
+export type ProposalState = IdlTypes<InterDao>['ProposalState']
+export const ProposalStates: Record<string, ProposalState> = {
+  Draft: { draft: {} },
+  Voting: { voting: {} },
+  Succeeded: { succeeded: {} },
+  Defeated: { defeated: {} },
+  Queued: { queued: {} },
+  Executing: { executing: {} },
+  Executed: { executed: {} },
+  Cancelled: { cancelled: {} },
+  Expired: { expired: {} },
+}
+
+export type HashAlgorithm = IdlTypes<InterDao>['HashAlgorithm']
+export const HashAlgorithms: Record<string, HashAlgorithm> = {
+  Sha256: { sha256: {} },
+  Keccak256: { keccak256: {} },
+  Blake3: { blake3: {} },
+}
+
 export type InvokedAccount = IdlTypes<InterDao>['InvokedAccount']
 
 type TypeDefDictionary<T extends IdlEvent[], Defined> = {
@@ -56,3 +78,11 @@ export type FeeOptions = {
   revenue: BN
   revenuemanAddress: string
 }
+
+export type ProposalBounds = {
+  minVotingDuration: BN
+  maxVotingDuration: BN
+  minNoticePeriod: BN
+  executionWindow: BN
+  retryWindow: BN
+}
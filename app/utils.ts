@@ -1,4 +1,4 @@
-import { BN, web3 } from '@project-serum/anchor'
+import { BN, web3 } from '@coral-xyz/anchor'
 import { programs } from '@metaplex/js'
 
 /**
@@ -40,6 +40,29 @@ export const findProposal = async (
   return proposalPublicKey
 }
 
+/**
+ * Find the content of a dao based on canonical bump
+ * @param nonce Content nonce of the dao
+ * @param daoPublicKey Dao public key
+ * @param programId InterDAO program public key
+ * @returns Content public key
+ */
+export const findContent = async (
+  nonce: BN,
+  daoPublicKey: web3.PublicKey,
+  programId: web3.PublicKey,
+) => {
+  const [contentPublicKey] = await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from('content'),
+      nonce.toArrayLike(Buffer, 'le', 8), // Browser compatibility
+      daoPublicKey.toBuffer(),
+    ],
+    programId,
+  )
+  return contentPublicKey
+}
+
 /**
  * Find the my receipt of an proposal based on canonical bump
  * @param index Receipt index
@@ -66,6 +89,29 @@ export const findReceipt = async (
   return receiptPublicKey
 }
 
+/**
+ * Find the voter record of a wallet on a proposal
+ * @param proposalPublicKey Proposal public key
+ * @param authorityPublicKey Voter public key
+ * @param programId InterDAO program public key
+ * @returns Voter record public key
+ */
+export const findVoterRecord = async (
+  proposalPublicKey: web3.PublicKey,
+  authorityPublicKey: web3.PublicKey,
+  programId: web3.PublicKey,
+) => {
+  const [voterRecordPublicKey] = await web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from('voter_record'),
+      proposalPublicKey.toBuffer(),
+      authorityPublicKey.toBuffer(),
+    ],
+    programId,
+  )
+  return voterRecordPublicKey
+}
+
 /**
  * Find the NFT metadata address
  * @param nftAddress public key
@@ -8,3 +8,6 @@ pub const I64_SIZE: usize = 8;
 pub const BOOL_SIZE: usize = 1;
 pub const VECTOR_OVERHEAD_SIZE: usize = 4;
 pub const INVOKED_ACCOUNT_SIZE: usize = 36;
+pub const CHECKPOINT_SIZE: usize = 24;
+pub const PROPOSAL_INSTRUCTION_KIND_SIZE: usize = 73;
+pub const MAX_METADATA_URI_SIZE: usize = 200;
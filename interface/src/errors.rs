@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+  #[msg("Operation overflowed")]
+  Overflow,
+  #[msg("Invalid accounts length")]
+  InvalidDataLength,
+  #[msg("Inconsistent proposal's accounts configuration")]
+  InconsistentProposal,
+  #[msg("The community isn't consenting on the proposal yet")]
+  NotConsentedProposal,
+  #[msg("The proposal isn't started yet")]
+  NotStartedProposal,
+  #[msg("The proposal isn't ended yet")]
+  NotEndedProposal,
+  #[msg("The proposal is started")]
+  StartedProposal,
+  #[msg("The proposal had been ended")]
+  EndedProposal,
+  #[msg("The proposal had been executed")]
+  ExecutedProposal,
+  #[msg("Invalid proposal instruction index")]
+  InvalidProposalIdx,
+  #[msg("No permission")]
+  NoPermission,
+  #[msg("Cannot derive the program address")]
+  NoBump,
+  #[msg("Cannot get current date")]
+  InvalidCurrentDate,
+  #[msg("Start date need to be greater than or equal to current date")]
+  InvalidStartDate,
+  #[msg("End date need to be greater than start date and current date")]
+  InvalidEndDate,
+  #[msg("Invalid NFT collection")]
+  InvalidNftCollection,
+  #[msg("Cannot compute the token transfer fee")]
+  InvalidTransferFee,
+  #[msg("Invalid veto guardians configuration")]
+  InvalidGuardians,
+  #[msg("The proposal had been vetoed")]
+  VetoedProposal,
+  #[msg("The proposal is still in the timelock window")]
+  TimelockedProposal,
+  #[msg("The timelock window had been passed")]
+  EndedTimelock,
+  #[msg("The mint isn't a governance mint of the DAO")]
+  InvalidGovernanceMint,
+  #[msg("The council mint must differ from the community mint and have a positive supply")]
+  InvalidCouncilMint,
+  #[msg("There is no pending authority transfer")]
+  NoPendingAuthority,
+  #[msg("The DAO must be autonomous to renounce its authority")]
+  NotAutonomousDao,
+  #[msg("Invalid proposal duration bounds")]
+  InvalidProposalBounds,
+  #[msg("Start date need to respect the DAO notice period")]
+  InvalidNoticePeriod,
+  #[msg("Voting duration need to be within the DAO bounds")]
+  InvalidVotingDuration,
+  #[msg("The proposal had been expired")]
+  ExpiredProposal,
+  #[msg("The proposal instruction had been marked as failed")]
+  FailedProposalInstruction,
+  #[msg("The proposal instruction can still be retried")]
+  RetryableProposalInstruction,
+  #[msg("All proposal instructions following the removed one must be passed in order")]
+  MissingFollowingProposalInstructions,
+  #[msg("The lock duration exceeds the maximum or shortens the current lock")]
+  InvalidLockDuration,
+  #[msg("The escrow is still locked or backing live votes")]
+  LockedEscrow,
+  #[msg("The escrow had no voting power at the proposal start")]
+  NoEscrowPower,
+  #[msg("The voter had voted in the opposite direction")]
+  ConflictingVoteDirection,
+  #[msg("Invalid proposal template")]
+  InvalidProposalTemplate,
+  #[msg("The master isn't the mint authority")]
+  InvalidMintAuthority,
+  #[msg("The author doesn't hold enough governance tokens to post")]
+  InsufficientHoldings,
+  #[msg("The parent content belongs to another discussion")]
+  InvalidContentParent,
+  #[msg("Content gating requires a fungible governance mint")]
+  InvalidContentThreshold,
+  #[msg("The metadata URI is too long, of an unsupported scheme or inconsistent with its hash")]
+  InvalidMetadataUri,
+  #[msg("The NFT metadata account is invalid")]
+  InvalidNftMetadata,
+  #[msg("The token account is not the associated token account of the token program")]
+  InvalidAssociatedTokenAccount,
+  #[msg("NFT DAOs cannot have a council")]
+  UnsupportedNftCouncil,
+  #[msg("The DAO only counts the escrow power snapshotted at the proposal start")]
+  SnapshotVoting,
+  #[msg("NFT DAOs cannot vote by snapshot")]
+  UnsupportedNftSnapshotVoting,
+}
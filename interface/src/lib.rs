@@ -0,0 +1,20 @@
+#![allow(clippy::result_large_err, clippy::derivable_impls)]
+//!
+//! Account layouts of the interDAO program, for programs and clients that only read its accounts
+//!
+//! The crate depends on anchor-lang alone. The program depends on it and re-exports its modules
+//! and program ID, so both always agree on the layouts.
+//!
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod errors;
+pub mod schema;
+pub mod traits;
+pub mod utils;
+
+pub use errors::ErrorCode;
+pub use schema::*;
+pub use traits::{Age, Consensus, Permission};
+
+declare_id!("xWfewaindgnhppdM9qpAhoDpWrdm1myRmgsPLPhGEuP");
@@ -0,0 +1,43 @@
+use crate::constants::*;
+use crate::schema::metadata::*;
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Content {
+  pub index: u64,
+  pub authority: Pubkey,
+  pub discriminator: [u8; 8],
+  pub metadata: [u8; 32],
+  // The discussed DAO, and proposal if any (default for DAO-wide entries)
+  pub dao: Pubkey,
+  pub proposal: Pubkey,
+  // The replied entry (default for top-level entries)
+  pub parent: Pubkey,
+  pub created_at: i64,
+  pub updated_at: i64,
+  // Append-only edit counter, zero until the first edit
+  pub revision: u64,
+  // Off-chain metadata, the URI is sized on demand
+  pub hash_algorithm: HashAlgorithm,
+  pub metadata_uri: String,
+}
+
+impl Content {
+  pub const LEN: usize = DISCRIMINATOR_SIZE
+    + U64_SIZE
+    + PUBKEY_SIZE
+    + U8_SIZE * 8
+    + U8_SIZE * 32
+    + PUBKEY_SIZE
+    + PUBKEY_SIZE
+    + PUBKEY_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + U64_SIZE
+    + U8_SIZE
+    + VECTOR_OVERHEAD_SIZE; // And the metadata URI
+
+  pub fn is_reply(&self) -> bool {
+    self.parent != Pubkey::default()
+  }
+}
@@ -0,0 +1,173 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::schema::metadata::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+///
+/// DAO mechanism
+///
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum DaoRegime {
+  Dictatorial, // Only Authority can create, approve and veto proposals
+  Democratic,  // Community can propose and Authority can approve. Authority and guardians can veto
+  Autonomous,  // Community can propose and consent (based on 51% rule). Only guardians can veto
+}
+impl Default for DaoRegime {
+  fn default() -> Self {
+    DaoRegime::Dictatorial
+  }
+}
+
+#[account]
+pub struct Dao {
+  pub master: Pubkey,
+  pub authority: Pubkey,
+  pub mint: Pubkey,
+  pub regime: DaoRegime,
+  pub supply: u64,
+  pub nonce: u64,
+  pub metadata: [u8; 32],
+  pub is_nft: bool,
+  pub is_public: bool,
+  // Veto guardians
+  pub guardians: Vec<Pubkey>,
+  pub veto_threshold: u8,
+  pub timelock: i64,
+  // Council house
+  pub council_mint: Pubkey,
+  pub council_supply: u64,
+  // Two-step authority transfer
+  pub pending_authority: Pubkey,
+  // Proposal duration bounds (in seconds). A zero execution window never expires
+  // Failing instructions may be skipped after the retry window
+  pub min_voting_duration: i64,
+  pub max_voting_duration: i64,
+  pub min_notice_period: i64,
+  pub execution_window: i64,
+  pub retry_window: i64,
+  // Execute or close proposals once the outcome can't be flipped
+  pub early_finalization: bool,
+  // Voters can't vote both for and against a proposal
+  pub one_direction_voting: bool,
+  // Voters only vote with the escrow power snapshotted at the proposal start
+  pub snapshot_voting: bool,
+  // Minimum governance token holdings to post content. Zero lets anyone post.
+  // Holdings are only checked when posting, not when editing
+  pub content_threshold: u64,
+  // Content addresses are derived from this counter so deleted ones are never reused
+  pub content_nonce: u64,
+  // Off-chain metadata, the URI is sized on demand
+  pub hash_algorithm: HashAlgorithm,
+  pub metadata_uri: String,
+}
+
+impl Dao {
+  pub const LEN: usize = DISCRIMINATOR_SIZE
+    + PUBKEY_SIZE
+    + PUBKEY_SIZE
+    + PUBKEY_SIZE
+    + U8_SIZE
+    + U64_SIZE
+    + U64_SIZE
+    + U8_SIZE * 32
+    + U8_SIZE
+    + U8_SIZE
+    + VECTOR_OVERHEAD_SIZE
+    + PUBKEY_SIZE * Dao::MAX_GUARDIANS
+    + U8_SIZE
+    + I64_SIZE
+    + PUBKEY_SIZE
+    + U64_SIZE
+    + PUBKEY_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + BOOL_SIZE
+    + BOOL_SIZE
+    + BOOL_SIZE
+    + U64_SIZE
+    + U64_SIZE
+    + U8_SIZE
+    + VECTOR_OVERHEAD_SIZE; // And the metadata URI
+
+  // Vetoes are recorded as a u8 bitmask on proposals
+  pub const MAX_GUARDIANS: usize = 8;
+  // Executors always get a chance to retry before anyone can fail an instruction
+  pub const MIN_RETRY_WINDOW: i64 = 10 * 60;
+
+  pub fn is_valid_proposal_bounds(
+    min_voting_duration: i64,
+    max_voting_duration: i64,
+    min_notice_period: i64,
+    execution_window: i64,
+    retry_window: i64,
+  ) -> bool {
+    min_voting_duration > 0
+      && max_voting_duration >= min_voting_duration
+      && min_notice_period >= 0
+      && execution_window >= 0
+      && retry_window >= Dao::MIN_RETRY_WINDOW
+      // A failing first instruction can still be failed before the proposal expires
+      && (execution_window == 0 || retry_window < execution_window)
+  }
+
+  pub fn validate_proposal_dates(&self, now: i64, start_date: i64, end_date: i64) -> Result<()> {
+    if start_date < now {
+      return err!(ErrorCode::InvalidStartDate);
+    }
+    if start_date
+      .checked_sub(now)
+      .ok_or(ErrorCode::InvalidStartDate)?
+      < self.min_notice_period
+    {
+      return err!(ErrorCode::InvalidNoticePeriod);
+    }
+    if end_date <= start_date || end_date <= now {
+      return err!(ErrorCode::InvalidEndDate);
+    }
+    let voting_duration = end_date
+      .checked_sub(start_date)
+      .ok_or(ErrorCode::InvalidEndDate)?;
+    if voting_duration < self.min_voting_duration || voting_duration > self.max_voting_duration {
+      return err!(ErrorCode::InvalidVotingDuration);
+    }
+    Ok(())
+  }
+
+  pub fn is_eligible_to_post(&self, holdings: u64) -> bool {
+    holdings >= self.content_threshold
+  }
+}
+
+impl Permission for Dao {
+  fn is_authorized_to_propose(&self, caller: Pubkey) -> bool {
+    match self.regime {
+      DaoRegime::Dictatorial => self.authority == caller,
+      DaoRegime::Democratic => true,
+      DaoRegime::Autonomous => true,
+    }
+  }
+  fn is_authorized_to_execute(&self, caller: Pubkey) -> bool {
+    match self.regime {
+      DaoRegime::Dictatorial => self.authority == caller,
+      DaoRegime::Democratic => self.authority == caller,
+      DaoRegime::Autonomous => true,
+    }
+  }
+  fn is_authorized_to_veto(&self, caller: Pubkey, guardians: &[Pubkey]) -> bool {
+    let is_guardian = guardians.contains(&caller);
+    match self.regime {
+      DaoRegime::Dictatorial => self.authority == caller,
+      DaoRegime::Democratic => self.authority == caller || is_guardian,
+      DaoRegime::Autonomous => is_guardian,
+    }
+  }
+  fn is_authorized_to_update(&self, caller: Pubkey) -> bool {
+    // The master only signs through executed proposals
+    self.authority == caller || self.master == caller
+  }
+}
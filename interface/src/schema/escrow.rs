@@ -0,0 +1,102 @@
+use crate::constants::*;
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+///
+/// Escrow balance history
+///
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub struct Checkpoint {
+  pub timestamp: i64,
+  pub amount: u64,
+  pub unlocked_date: i64,
+}
+
+#[account]
+pub struct Escrow {
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub mint: Pubkey,
+  pub amount: u64,
+  pub locked_date: i64,
+  pub unlocked_date: i64,
+  // The latest end date of proposals voted with this escrow
+  pub voting_until: i64,
+  // Sorted by timestamp. The two oldest ones are merged once full
+  pub checkpoints: Vec<Checkpoint>,
+}
+
+impl Escrow {
+  pub const LEN: usize = DISCRIMINATOR_SIZE
+    + PUBKEY_SIZE
+    + PUBKEY_SIZE
+    + PUBKEY_SIZE
+    + U64_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + VECTOR_OVERHEAD_SIZE
+    + CHECKPOINT_SIZE * Escrow::MAX_CHECKPOINTS;
+
+  // Locking for the max duration grants the full amount as voting power
+  pub const MAX_LOCK_DURATION: i64 = 4 * 365 * 24 * 60 * 60;
+  pub const MAX_CHECKPOINTS: usize = 16;
+
+  ///
+  /// Record the current balance and lock.
+  /// Locks only grow, so once full the second oldest checkpoint is dropped:
+  /// snapshots in its span fall back to the smaller oldest one instead of losing all power
+  ///
+  pub fn checkpoint(&mut self, now: i64) {
+    let checkpoint = Checkpoint {
+      timestamp: now,
+      amount: self.amount,
+      unlocked_date: self.unlocked_date,
+    };
+    match self.checkpoints.last_mut() {
+      Some(last) if last.timestamp == now => *last = checkpoint,
+      _ => {
+        if self.checkpoints.len() >= Escrow::MAX_CHECKPOINTS {
+          self.checkpoints.remove(1);
+        }
+        self.checkpoints.push(checkpoint);
+      }
+    }
+  }
+
+  ///
+  /// The latest checkpoint at the snapshot date
+  ///
+  pub fn checkpoint_at(&self, snapshot_date: i64) -> Option<&Checkpoint> {
+    self
+      .checkpoints
+      .iter()
+      .rev()
+      .find(|checkpoint| checkpoint.timestamp <= snapshot_date)
+  }
+
+  ///
+  /// The vote-escrow power at the snapshot date. It linearly decays to zero at the unlocked date
+  ///
+  pub fn voting_power_at(&self, snapshot_date: i64) -> Option<u64> {
+    let checkpoint = match self.checkpoint_at(snapshot_date) {
+      Some(checkpoint) => checkpoint,
+      None => return Some(0),
+    };
+    if snapshot_date >= checkpoint.unlocked_date {
+      return Some(0);
+    }
+    let remaining = checkpoint
+      .unlocked_date
+      .checked_sub(snapshot_date)?
+      .min(Escrow::MAX_LOCK_DURATION);
+    let power = u128::from(checkpoint.amount)
+      .checked_mul(u128::try_from(remaining).ok()?)?
+      .checked_div(u128::try_from(Escrow::MAX_LOCK_DURATION).ok()?)?;
+    u64::try_from(power).ok()
+  }
+
+  pub fn is_withdrawable(&self, now: i64) -> bool {
+    now >= self.unlocked_date && now > self.voting_until
+  }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+///
+/// The algorithm of a metadata hash
+///
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum HashAlgorithm {
+  Sha256,    // The digest of IPFS CIDv0 and of most CIDv1
+  Keccak256, // Ethereum-style content hashes
+  Blake3,    // IPFS CIDv1 with a blake3 multihash
+}
+impl Default for HashAlgorithm {
+  fn default() -> Self {
+    HashAlgorithm::Sha256
+  }
+}
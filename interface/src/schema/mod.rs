@@ -8,3 +8,9 @@ pub mod content;
 pub use content::*;
 pub mod proposal_instruction;
 pub use proposal_instruction::*;
+pub mod escrow;
+pub use escrow::*;
+pub mod voter_record;
+pub use voter_record::*;
+pub mod metadata;
+pub use metadata::*;
@@ -0,0 +1,905 @@
+use crate::constants::*;
+use crate::schema::{
+  dao::Dao, dao::DaoRegime, metadata::HashAlgorithm, receipt::Receipt, receipt::ReceiptAction,
+};
+use crate::traits::{Age, Consensus};
+use crate::utils::current_timestamp;
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+///
+/// Consensus mechanism
+///
+/// Staked Token Counter
+/// The voted power is counted by the number of staked tokens to the proposal.
+/// Voters can unstake anytime, but the voted power will be excluded.
+///
+/// Locked Token Counter
+/// The voted power is counted by multiplication of the committed time and the number of locked tokens to the proposal.
+/// Voters can't unlock during the campaign.
+///
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ConsensusMechanism {
+  StakedTokenCounter,
+  LockedTokenCounter,
+}
+impl Default for ConsensusMechanism {
+  fn default() -> Self {
+    ConsensusMechanism::StakedTokenCounter
+  }
+}
+
+///
+/// DAO quorum
+///
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ConsensusQuorum {
+  OneThird, // The porposal's voting power must be greater 1/3 total power to be passed
+  Half,     // The porposal's voting power must be greater 1/2 total power to be passed
+  TwoThird, // The porposal's voting power must be greater 2/3 total power to be passed
+}
+impl Default for ConsensusQuorum {
+  fn default() -> Self {
+    ConsensusQuorum::Half
+  }
+}
+
+///
+/// Voting house
+///
+/// Community: holders of the DAO mint
+/// Council: holders of the DAO council mint (dual-house DAOs only)
+///
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum House {
+  Community,
+  Council,
+}
+impl Default for House {
+  fn default() -> Self {
+    House::Community
+  }
+}
+
+///
+/// Proposal state
+///
+/// Draft: before the start date
+/// Voting: the campaign is running
+/// Succeeded: the community consented and the proposal is waiting for execution
+/// Defeated: the community didn't consent
+/// Queued: the proposal succeeded but is still in the timelock window
+/// Executing: some of the proposal instructions were executed
+/// Executed: all of the proposal instructions were processed (some may have failed)
+/// Cancelled: the proposal was vetoed
+/// Expired: the proposal succeeded but wasn't executed within the execution window
+///
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ProposalState {
+  Draft,
+  Voting,
+  Succeeded,
+  Defeated,
+  Queued,
+  Executing,
+  Executed,
+  Cancelled,
+  Expired,
+}
+impl Default for ProposalState {
+  fn default() -> Self {
+    ProposalState::Draft
+  }
+}
+impl ProposalState {
+  pub fn is_final(&self) -> bool {
+    matches!(
+      self,
+      ProposalState::Defeated
+        | ProposalState::Executed
+        | ProposalState::Cancelled
+        | ProposalState::Expired
+    )
+  }
+  ///
+  /// The proposal passed and its instructions can run or already ran.
+  /// Queued proposals may still be vetoed and expired ones will never run.
+  ///
+  pub fn is_passed(&self) -> bool {
+    matches!(
+      self,
+      ProposalState::Succeeded | ProposalState::Executing | ProposalState::Executed
+    )
+  }
+}
+
+///
+/// Proposal status
+///
+/// A stable snapshot of the proposal outcome returned to other programs.
+/// The state is evaluated at the timestamp without being recorded.
+///
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ProposalStatus {
+  pub proposal: Pubkey,
+  pub dao: Pubkey,
+  pub index: u64,
+  pub timestamp: i64,
+  pub state: ProposalState,
+  pub is_passed: bool,
+  pub is_final: bool,
+  pub executed: bool,
+  pub vetoed: bool,
+  pub voting_for_power: u128,
+  pub voting_against_power: u128,
+  pub total_power: u128,
+  pub council_for_power: u128,
+  pub council_against_power: u128,
+  pub council_total_power: u128,
+}
+
+#[account]
+pub struct Proposal {
+  pub index: u64,
+  pub creator: Pubkey,
+  pub dao: Pubkey,
+  pub regime: DaoRegime,
+  pub consensus_mechanism: ConsensusMechanism,
+  pub consensus_quorum: ConsensusQuorum,
+  pub total_instruction: u8,
+  pub total_executed: u8,
+  pub executed: bool,
+  pub voting_for_power: u128,
+  pub voting_against_power: u128,
+  pub supply: u64,
+  pub start_date: i64,
+  pub end_date: i64,
+  pub metadata: [u8; 32],
+  // Veto guardians, snapshotted at creation
+  pub timelock: i64,
+  pub guardians: Vec<Pubkey>,
+  pub veto_threshold: u8,
+  pub vetoes: u8,
+  pub vetoed: bool,
+  // Council house
+  pub council_mint: Pubkey,
+  pub council_supply: u64,
+  pub council_for_power: u128,
+  pub council_against_power: u128,
+  // Execute or close once the outcome can't be flipped
+  pub early_finalization: bool,
+  pub decided_date: i64,
+  pub execution_window: i64,
+  pub state: ProposalState,
+  // Failed instructions
+  pub independent_instructions: bool,
+  pub total_failed: u8,
+  pub retry_window: i64,
+  pub last_processed_date: i64,
+  // One voting direction per voter
+  pub one_direction_voting: bool,
+  // Only escrow power snapshotted at the start date counts
+  pub snapshot_voting: bool,
+  // Off-chain metadata, the URI is sized on demand
+  pub hash_algorithm: HashAlgorithm,
+  pub metadata_uri: String,
+}
+
+impl Proposal {
+  pub const LEN: usize = DISCRIMINATOR_SIZE
+    + U64_SIZE
+    + PUBKEY_SIZE * 2
+    + U8_SIZE * 5
+    + BOOL_SIZE
+    + U128_SIZE
+    + U128_SIZE
+    + U64_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + U8_SIZE * 32
+    + I64_SIZE
+    + VECTOR_OVERHEAD_SIZE
+    + PUBKEY_SIZE * Dao::MAX_GUARDIANS
+    + U8_SIZE
+    + U8_SIZE
+    + BOOL_SIZE
+    + PUBKEY_SIZE
+    + U64_SIZE
+    + U128_SIZE
+    + U128_SIZE
+    + BOOL_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + U8_SIZE
+    + BOOL_SIZE
+    + U8_SIZE
+    + I64_SIZE
+    + I64_SIZE
+    + BOOL_SIZE
+    + BOOL_SIZE
+    + U8_SIZE
+    + VECTOR_OVERHEAD_SIZE; // And the metadata URI
+
+  pub fn is_dual_house(&self) -> bool {
+    self.council_mint != Pubkey::default()
+  }
+
+  pub fn house_of(&self, mint: Pubkey) -> House {
+    if self.is_dual_house() && mint == self.council_mint {
+      House::Council
+    } else {
+      House::Community
+    }
+  }
+
+  pub fn power_at(&self, amount: u64, locked_date: i64) -> Option<u128> {
+    let power = match self.consensus_mechanism {
+      ConsensusMechanism::StakedTokenCounter => u128::from(amount),
+      ConsensusMechanism::LockedTokenCounter => {
+        u128::try_from(self.end_date.checked_sub(locked_date)?)
+          .ok()?
+          .checked_mul(u128::from(amount))?
+      }
+    };
+    Some(power)
+  }
+
+  pub fn total_power(&self, house: House) -> Option<u128> {
+    let supply = match house {
+      House::Community => self.supply,
+      House::Council => self.council_supply,
+    };
+    let total_power = match self.consensus_mechanism {
+      ConsensusMechanism::StakedTokenCounter => u128::from(supply),
+      ConsensusMechanism::LockedTokenCounter => {
+        u128::try_from(self.end_date.checked_sub(self.start_date)?)
+          .ok()?
+          .checked_mul(u128::from(supply))?
+      }
+    };
+
+    Some(total_power)
+  }
+
+  pub fn tally(&self, house: House) -> (u128, u128) {
+    match house {
+      House::Community => (self.voting_for_power, self.voting_against_power),
+      House::Council => (self.council_for_power, self.council_against_power),
+    }
+  }
+
+  pub fn voting_power(&self, house: House) -> u128 {
+    let (voting_for_power, voting_against_power) = self.tally(house);
+    voting_for_power.saturating_sub(voting_against_power)
+  }
+
+  pub fn threshold(&self, house: House) -> Option<u128> {
+    let total_power = self.total_power(house)?;
+    match self.consensus_quorum {
+      ConsensusQuorum::OneThird => total_power.checked_div(3),
+      ConsensusQuorum::Half => total_power.checked_div(2),
+      ConsensusQuorum::TwoThird => total_power.checked_mul(2)?.checked_div(3),
+    }
+  }
+
+  ///
+  /// The power that hasn't been cast yet, bounded by the total power
+  ///
+  pub fn remaining_power(&self, house: House) -> Option<u128> {
+    let (voting_for_power, voting_against_power) = self.tally(house);
+    let cast_power = voting_for_power.checked_add(voting_against_power)?;
+    Some(self.total_power(house)?.saturating_sub(cast_power))
+  }
+
+  ///
+  /// The house passes even if all remaining power votes against
+  ///
+  pub fn is_irreversibly_passed(&self, house: House) -> Option<bool> {
+    let (voting_for_power, voting_against_power) = self.tally(house);
+    let worst_against_power = voting_against_power.checked_add(self.remaining_power(house)?)?;
+    Some(voting_for_power.saturating_sub(worst_against_power) > self.threshold(house)?)
+  }
+
+  ///
+  /// The house fails even if all remaining power votes for
+  ///
+  pub fn is_irreversibly_failed(&self, house: House) -> Option<bool> {
+    let (voting_for_power, voting_against_power) = self.tally(house);
+    let best_for_power = voting_for_power.checked_add(self.remaining_power(house)?)?;
+    Some(best_for_power.saturating_sub(voting_against_power) <= self.threshold(house)?)
+  }
+
+  ///
+  /// The single place where the proposal state moves forward.
+  /// Final states are recorded permanently.
+  ///
+  pub fn transition(&mut self) -> ProposalState {
+    self.transition_at(current_timestamp().unwrap_or(0))
+  }
+
+  pub fn transition_at(&mut self, now: i64) -> ProposalState {
+    if self.state.is_final() {
+      return self.state;
+    }
+    self.record_decision_at(now);
+    self.state = if self.is_executed() {
+      ProposalState::Executed
+    } else if self.is_vetoed() {
+      ProposalState::Cancelled
+    } else if !self.is_started_at(now) {
+      ProposalState::Draft
+    } else if !self.is_ended_at(now) && !self.is_decided() {
+      ProposalState::Voting
+    } else if !self.is_consented() {
+      ProposalState::Defeated
+    } else if self.total_executed > 0 || self.total_failed > 0 {
+      ProposalState::Executing
+    } else if self.is_timelocked_at(now) {
+      ProposalState::Queued
+    } else if self.is_expired_at(now) {
+      ProposalState::Expired
+    } else {
+      ProposalState::Succeeded
+    };
+    self.state
+  }
+
+  ///
+  /// Record when the outcome became irreversible during the voting window.
+  /// The timelock of an early decision starts from that date instead of the end date.
+  ///
+  pub fn record_decision_at(&mut self, now: i64) {
+    if self.decided_date == 0
+      && self.is_started_at(now)
+      && !self.is_ended_at(now)
+      && self.is_decided()
+    {
+      self.decided_date = now;
+    }
+  }
+
+  ///
+  /// The date from which the timelock runs
+  ///
+  pub fn decision_date(&self) -> i64 {
+    if self.decided_date == 0 {
+      self.end_date
+    } else {
+      self.decided_date.min(self.end_date)
+    }
+  }
+
+  ///
+  /// Evaluate the state at the timestamp without recording it
+  ///
+  pub fn status_at(&self, key: Pubkey, now: i64) -> ProposalStatus {
+    let state = self.clone().transition_at(now);
+    ProposalStatus {
+      proposal: key,
+      dao: self.dao,
+      index: self.index,
+      timestamp: now,
+      state,
+      is_passed: state.is_passed(),
+      is_final: state.is_final(),
+      executed: self.executed,
+      vetoed: self.vetoed,
+      voting_for_power: self.voting_for_power,
+      voting_against_power: self.voting_against_power,
+      total_power: self.total_power(House::Community).unwrap_or(0),
+      council_for_power: self.council_for_power,
+      council_against_power: self.council_against_power,
+      council_total_power: if self.is_dual_house() {
+        self.total_power(House::Council).unwrap_or(0)
+      } else {
+        0
+      },
+    }
+  }
+
+  ///
+  /// Instructions run in order, failed ones included
+  ///
+  pub fn next_instruction_index(&self) -> Option<u8> {
+    self.total_executed.checked_add(self.total_failed)
+  }
+
+  pub fn is_vetoed(&self) -> bool {
+    self.vetoed
+  }
+
+  pub fn guardian_index(&self, caller: Pubkey) -> Option<usize> {
+    self
+      .guardians
+      .iter()
+      .position(|guardian| *guardian == caller)
+  }
+
+  pub fn veto(&mut self, guardian_index: Option<usize>) -> Option<bool> {
+    match guardian_index {
+      // Guardians sign one by one until the veto threshold is reached.
+      // Vetoes index the snapshotted guardians, so later changes to the DAO can't shift them
+      Some(index) => {
+        self.vetoes |= 1u8.checked_shl(u32::try_from(index).ok()?)?;
+        if self.vetoes.count_ones() >= self.veto_threshold.max(1) as u32 {
+          self.vetoed = true;
+        }
+      }
+      // The authority vetoes at once
+      None => self.vetoed = true,
+    }
+    Some(self.vetoed)
+  }
+
+  pub fn is_more_than_one_third(&self, house: House) -> Option<bool> {
+    let total_power = self.total_power(house)?;
+    let threshold = total_power.checked_div(3)?;
+    let voting_power = self.voting_power(house);
+    if voting_power > threshold {
+      Some(true)
+    } else {
+      Some(false)
+    }
+  }
+
+  pub fn is_more_than_half(&self, house: House) -> Option<bool> {
+    let total_power = self.total_power(house)?;
+    let threshold = total_power.checked_div(2)?;
+    let voting_power = self.voting_power(house);
+    if voting_power > threshold {
+      Some(true)
+    } else {
+      Some(false)
+    }
+  }
+
+  pub fn is_more_than_two_third(&self, house: House) -> Option<bool> {
+    let total_power = self.total_power(house)?;
+    let threshold = total_power.checked_mul(2)?.checked_div(3)?;
+    let voting_power = self.voting_power(house);
+    if voting_power > threshold {
+      Some(true)
+    } else {
+      Some(false)
+    }
+  }
+}
+
+impl Consensus for Proposal {
+  fn calculate_my_power_at(&self, amount: u64, now: i64, receipt: &mut Receipt) -> Option<u128> {
+    receipt.locked_date = now;
+    self.power_at(amount, now)
+  }
+  fn vote_for_at(&mut self, amount: u64, now: i64, receipt: &mut Receipt) -> Option<(u128, u128)> {
+    let power = self.calculate_my_power_at(amount, now, receipt)?;
+    // Update receipt data
+    receipt.amount = amount;
+    receipt.power = power;
+    receipt.action = ReceiptAction::VoteFor;
+    // Update proposal data
+    let voting_for_power = match self.house_of(receipt.mint) {
+      House::Community => &mut self.voting_for_power,
+      House::Council => &mut self.council_for_power,
+    };
+    *voting_for_power = voting_for_power.checked_add(power)?;
+    let voting_for_power = *voting_for_power;
+    self.record_decision_at(now);
+    Some((power, voting_for_power))
+  }
+  fn vote_against_at(
+    &mut self,
+    amount: u64,
+    now: i64,
+    receipt: &mut Receipt,
+  ) -> Option<(u128, u128)> {
+    let power = self.calculate_my_power_at(amount, now, receipt)?;
+    // Update receipt data
+    receipt.amount = amount;
+    receipt.power = power;
+    receipt.action = ReceiptAction::VoteAgainst;
+    // Update proposal data
+    let voting_against_power = match self.house_of(receipt.mint) {
+      House::Community => &mut self.voting_against_power,
+      House::Council => &mut self.council_against_power,
+    };
+    *voting_against_power = voting_against_power.checked_add(power)?;
+    let voting_against_power = *voting_against_power;
+    self.record_decision_at(now);
+    Some((power, voting_against_power))
+  }
+  fn increase_vote_at(
+    &mut self,
+    amount: u64,
+    now: i64,
+    receipt: &mut Receipt,
+  ) -> Option<(u128, u128)> {
+    // Only the new portion is weighted from now on
+    let power = self.power_at(amount, now)?;
+    // Update receipt data
+    receipt.amount = receipt.amount.checked_add(amount)?;
+    receipt.power = receipt.power.checked_add(power)?;
+    // Update proposal data
+    let voting_power = match (self.house_of(receipt.mint), receipt.action) {
+      (House::Community, ReceiptAction::VoteFor) => &mut self.voting_for_power,
+      (House::Community, ReceiptAction::VoteAgainst) => &mut self.voting_against_power,
+      (House::Council, ReceiptAction::VoteFor) => &mut self.council_for_power,
+      (House::Council, ReceiptAction::VoteAgainst) => &mut self.council_against_power,
+    };
+    *voting_power = voting_power.checked_add(power)?;
+    let voting_power = *voting_power;
+    self.record_decision_at(now);
+    Some((power, voting_power))
+  }
+  fn is_consented(&self) -> bool {
+    let is_house_consented = |house: House| match self.consensus_quorum {
+      ConsensusQuorum::OneThird => self.is_more_than_one_third(house).unwrap_or(false),
+      ConsensusQuorum::Half => self.is_more_than_half(house).unwrap_or(false),
+      ConsensusQuorum::TwoThird => self.is_more_than_two_third(house).unwrap_or(false),
+    };
+    // Dual-house DAOs require both quorums
+    is_house_consented(House::Community)
+      && (!self.is_dual_house() || is_house_consented(House::Council))
+  }
+  fn is_decided(&self) -> bool {
+    if !self.early_finalization {
+      return false;
+    }
+    let is_passed = |house: House| self.is_irreversibly_passed(house).unwrap_or(false);
+    let is_failed = |house: House| self.is_irreversibly_failed(house).unwrap_or(false);
+    let passed =
+      is_passed(House::Community) && (!self.is_dual_house() || is_passed(House::Council));
+    let failed = is_failed(House::Community) || (self.is_dual_house() && is_failed(House::Council));
+    passed || failed
+  }
+}
+
+impl Age for Proposal {
+  fn is_started_at(&self, now: i64) -> bool {
+    now >= self.start_date
+  }
+  fn is_ended_at(&self, now: i64) -> bool {
+    now > self.end_date
+  }
+  fn is_timelocked_at(&self, now: i64) -> bool {
+    match self.decision_date().checked_add(self.timelock) {
+      Some(unlocked_date) => now <= unlocked_date,
+      None => true,
+    }
+  }
+  fn is_expired_at(&self, now: i64) -> bool {
+    if self.execution_window == 0 {
+      return false;
+    }
+    match self
+      .decision_date()
+      .checked_add(self.timelock)
+      .and_then(|unlocked_date| unlocked_date.checked_add(self.execution_window))
+    {
+      Some(expired_date) => now > expired_date,
+      None => false,
+    }
+  }
+  fn is_retry_window_passed_at(&self, now: i64) -> bool {
+    let last_date = match self.decision_date().checked_add(self.timelock) {
+      Some(unlocked_date) => unlocked_date.max(self.last_processed_date),
+      None => return false,
+    };
+    match last_date.checked_add(self.retry_window) {
+      Some(retry_date) => now > retry_date,
+      None => false,
+    }
+  }
+  fn is_executed(&self) -> bool {
+    self.executed
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use proptest::prelude::*;
+
+  const START: i64 = 1_000;
+  const END: i64 = 2_000;
+
+  fn proposal(consensus_mechanism: ConsensusMechanism, supply: u64) -> Proposal {
+    Proposal {
+      index: 0,
+      creator: Pubkey::default(),
+      dao: Pubkey::default(),
+      regime: DaoRegime::Autonomous,
+      consensus_mechanism,
+      consensus_quorum: ConsensusQuorum::Half,
+      total_instruction: 1,
+      total_executed: 0,
+      executed: false,
+      voting_for_power: 0,
+      voting_against_power: 0,
+      supply,
+      start_date: START,
+      end_date: END,
+      metadata: [0; 32],
+      timelock: 0,
+      guardians: vec![],
+      veto_threshold: 0,
+      vetoes: 0,
+      vetoed: false,
+      council_mint: Pubkey::default(),
+      council_supply: 0,
+      council_for_power: 0,
+      council_against_power: 0,
+      early_finalization: false,
+      decided_date: 0,
+      execution_window: 0,
+      state: ProposalState::Draft,
+      independent_instructions: false,
+      total_failed: 0,
+      retry_window: 0,
+      last_processed_date: 0,
+      one_direction_voting: false,
+      snapshot_voting: false,
+      hash_algorithm: HashAlgorithm::Sha256,
+      metadata_uri: String::new(),
+    }
+  }
+
+  fn receipt() -> Receipt {
+    Receipt {
+      index: 0,
+      authority: Pubkey::default(),
+      proposal: Pubkey::default(),
+      mint: Pubkey::default(),
+      amount: 0,
+      power: 0,
+      locked_date: 0,
+      unlocked_date: 0,
+      action: ReceiptAction::VoteFor,
+      escrow: Pubkey::default(),
+    }
+  }
+
+  fn rank(state: ProposalState) -> u8 {
+    match state {
+      ProposalState::Draft => 0,
+      ProposalState::Voting => 1,
+      ProposalState::Defeated | ProposalState::Queued => 2,
+      ProposalState::Succeeded => 3,
+      ProposalState::Expired => 4,
+      _ => u8::MAX,
+    }
+  }
+
+  #[test]
+  fn test_total_power() {
+    let staked = proposal(ConsensusMechanism::StakedTokenCounter, 100);
+    assert_eq!(staked.total_power(House::Community), Some(100));
+    let locked = proposal(ConsensusMechanism::LockedTokenCounter, 100);
+    assert_eq!(locked.total_power(House::Community), Some(100_000));
+    // The widest dates can't be subtracted
+    let mut overflowed = proposal(ConsensusMechanism::LockedTokenCounter, u64::MAX);
+    overflowed.start_date = i64::MIN;
+    overflowed.end_date = i64::MAX;
+    assert_eq!(overflowed.total_power(House::Community), None);
+  }
+
+  #[test]
+  fn test_threshold_boundaries() {
+    let mut proposal = proposal(ConsensusMechanism::StakedTokenCounter, 10);
+    // Thresholds are floored and strictly exceeded
+    proposal.voting_for_power = 3;
+    assert_eq!(
+      proposal.is_more_than_one_third(House::Community),
+      Some(false)
+    );
+    proposal.voting_for_power = 4;
+    assert_eq!(
+      proposal.is_more_than_one_third(House::Community),
+      Some(true)
+    );
+    proposal.voting_for_power = 5;
+    assert_eq!(proposal.is_more_than_half(House::Community), Some(false));
+    proposal.voting_for_power = 6;
+    assert_eq!(proposal.is_more_than_half(House::Community), Some(true));
+    proposal.voting_for_power = 6;
+    assert_eq!(
+      proposal.is_more_than_two_third(House::Community),
+      Some(false)
+    );
+    proposal.voting_for_power = 7;
+    assert_eq!(
+      proposal.is_more_than_two_third(House::Community),
+      Some(true)
+    );
+  }
+
+  #[test]
+  fn test_voting_power_saturates() {
+    let mut proposal = proposal(ConsensusMechanism::StakedTokenCounter, 10);
+    proposal.voting_for_power = 3;
+    proposal.voting_against_power = 7;
+    assert_eq!(proposal.voting_power(House::Community), 0);
+  }
+
+  #[test]
+  fn test_locked_power_at_boundaries() {
+    let proposal = proposal(ConsensusMechanism::LockedTokenCounter, 100);
+    let mut receipt = receipt();
+    assert_eq!(
+      proposal.calculate_my_power_at(10, START, &mut receipt),
+      Some(10_000)
+    );
+    assert_eq!(receipt.locked_date, START);
+    assert_eq!(
+      proposal.calculate_my_power_at(10, END, &mut receipt),
+      Some(0)
+    );
+    assert_eq!(
+      proposal.calculate_my_power_at(10, END + 1, &mut receipt),
+      None
+    );
+  }
+
+  #[test]
+  fn test_early_decision_starts_the_timelock() {
+    let mut proposal = proposal(ConsensusMechanism::StakedTokenCounter, 10);
+    proposal.timelock = 50;
+    proposal.voting_for_power = 8;
+    // Without early finalization the outcome waits for the end date
+    assert_eq!(proposal.transition_at(START + 10), ProposalState::Voting);
+    assert_eq!(proposal.decided_date, 0);
+    assert!(proposal.is_timelocked_at(END + 50));
+
+    proposal.early_finalization = true;
+    assert_eq!(proposal.transition_at(START + 10), ProposalState::Queued);
+    assert_eq!(proposal.decided_date, START + 10);
+    assert!(proposal.is_timelocked_at(START + 60));
+    assert_eq!(proposal.transition_at(START + 61), ProposalState::Succeeded);
+    // The decision date is kept once recorded
+    proposal.record_decision_at(START + 20);
+    assert_eq!(proposal.decided_date, START + 10);
+  }
+
+  #[test]
+  fn test_age_boundaries() {
+    let mut proposal = proposal(ConsensusMechanism::StakedTokenCounter, 10);
+    proposal.timelock = 50;
+    proposal.execution_window = 100;
+    proposal.retry_window = 10;
+    assert!(!proposal.is_started_at(START - 1));
+    assert!(proposal.is_started_at(START));
+    assert!(!proposal.is_ended_at(END));
+    assert!(proposal.is_ended_at(END + 1));
+    assert!(proposal.is_timelocked_at(END + 50));
+    assert!(!proposal.is_timelocked_at(END + 51));
+    assert!(!proposal.is_expired_at(END + 150));
+    assert!(proposal.is_expired_at(END + 151));
+    assert!(!proposal.is_retry_window_passed_at(END + 60));
+    assert!(proposal.is_retry_window_passed_at(END + 61));
+    // No execution window never expires
+    proposal.execution_window = 0;
+    assert!(!proposal.is_expired_at(i64::MAX));
+    // An overflowed timelock never unlocks
+    proposal.timelock = i64::MAX;
+    assert!(proposal.is_timelocked_at(i64::MAX));
+  }
+
+  #[test]
+  fn test_passed_states() {
+    for state in [
+      ProposalState::Draft,
+      ProposalState::Voting,
+      ProposalState::Defeated,
+      ProposalState::Queued,
+      ProposalState::Cancelled,
+      ProposalState::Expired,
+    ] {
+      assert!(!state.is_passed());
+    }
+    for state in [
+      ProposalState::Succeeded,
+      ProposalState::Executing,
+      ProposalState::Executed,
+    ] {
+      assert!(state.is_passed());
+    }
+  }
+
+  #[test]
+  fn test_transition_at() {
+    let mut proposal = proposal(ConsensusMechanism::StakedTokenCounter, 10);
+    assert_eq!(proposal.transition_at(START - 1), ProposalState::Draft);
+    assert_eq!(proposal.transition_at(START), ProposalState::Voting);
+    assert_eq!(proposal.transition_at(END), ProposalState::Voting);
+    proposal.voting_for_power = 6;
+    assert_eq!(proposal.transition_at(END + 1), ProposalState::Succeeded);
+    proposal.voting_for_power = 5;
+    assert_eq!(proposal.transition_at(END + 1), ProposalState::Defeated);
+    // Final states are recorded
+    proposal.voting_for_power = 6;
+    assert_eq!(proposal.transition_at(END + 1), ProposalState::Defeated);
+  }
+
+  proptest! {
+    #[test]
+    fn prop_thresholds_are_exact(supply in any::<u64>(), votes in any::<u64>()) {
+      let mut proposal = proposal(ConsensusMechanism::StakedTokenCounter, supply);
+      proposal.voting_for_power = votes as u128;
+      let (supply, votes) = (supply as u128, votes as u128);
+      let one_third = proposal.is_more_than_one_third(House::Community);
+      prop_assert_eq!(one_third, Some(3 * votes > supply));
+      let half = proposal.is_more_than_half(House::Community);
+      prop_assert_eq!(half, Some(2 * votes > supply));
+      let two_third = proposal.is_more_than_two_third(House::Community);
+      prop_assert_eq!(two_third, Some(3 * votes > 2 * supply));
+    }
+
+    #[test]
+    fn prop_quorums_are_nested(
+      supply in any::<u64>(),
+      for_power in any::<u64>(),
+      against_power in any::<u64>(),
+    ) {
+      let mut proposal = proposal(ConsensusMechanism::StakedTokenCounter, supply);
+      proposal.voting_for_power = for_power as u128;
+      proposal.voting_against_power = against_power as u128;
+      let consented = |proposal: &mut Proposal, quorum: ConsensusQuorum| {
+        proposal.consensus_quorum = quorum;
+        proposal.is_consented()
+      };
+      if consented(&mut proposal, ConsensusQuorum::TwoThird) {
+        prop_assert!(consented(&mut proposal, ConsensusQuorum::Half));
+      }
+      if consented(&mut proposal, ConsensusQuorum::Half) {
+        prop_assert!(consented(&mut proposal, ConsensusQuorum::OneThird));
+      }
+    }
+
+    #[test]
+    fn prop_locked_power_never_overflows(
+      supply in any::<u64>(),
+      amount in any::<u64>(),
+      start_date in any::<i64>(),
+      end_date in any::<i64>(),
+      now in any::<i64>(),
+    ) {
+      let mut proposal = proposal(ConsensusMechanism::LockedTokenCounter, supply);
+      proposal.start_date = start_date;
+      proposal.end_date = end_date;
+      let total_power = proposal.total_power(House::Community);
+      let is_valid = |later: i64, earlier: i64| {
+        later.checked_sub(earlier).is_some_and(|duration| duration >= 0)
+      };
+      prop_assert_eq!(total_power.is_some(), is_valid(end_date, start_date));
+      let power = proposal.calculate_my_power_at(amount, now, &mut receipt());
+      prop_assert_eq!(power.is_some(), is_valid(end_date, now));
+      // Votes cast in the window never outweigh the same amount locked for the whole campaign
+      if let (Some(power), true) = (power, now >= start_date) {
+        let duration = (end_date as i128 - start_date as i128) as u128;
+        prop_assert!(power <= duration * amount as u128);
+      }
+    }
+
+    #[test]
+    fn prop_states_move_forward(
+      timelock in 0..1_000i64,
+      execution_window in 0..1_000i64,
+      for_power in 0..20u128,
+      mut dates in proptest::collection::vec(START - 100..END + 3_000, 1..20),
+    ) {
+      let mut proposal = proposal(ConsensusMechanism::StakedTokenCounter, 10);
+      proposal.timelock = timelock;
+      proposal.execution_window = execution_window;
+      proposal.voting_for_power = for_power;
+      dates.sort_unstable();
+      let ranks: Vec<u8> = dates
+        .iter()
+        .map(|now| rank(proposal.transition_at(*now)))
+        .collect();
+      prop_assert!(ranks.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+  }
+}
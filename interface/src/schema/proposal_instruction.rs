@@ -0,0 +1,108 @@
+use crate::constants::*;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct InvokedAccount {
+  pub pubkey: Pubkey,
+  pub is_signer: bool,
+  pub is_writable: bool,
+  pub is_master: bool,
+}
+
+///
+/// Proposal instruction kind
+///
+/// Raw instructions carry client-serialized data and accounts.
+/// Templates are built and validated on-chain so voters can audit typed actions.
+///
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ProposalInstructionKind {
+  Raw,
+  // SPL token transfer from the master associated token account
+  TokenTransfer {
+    mint: Pubkey,
+    dst: Pubkey,
+    amount: u64,
+  },
+  // SOL transfer from the master
+  SolTransfer {
+    dst: Pubkey,
+    lamports: u64,
+  },
+  // Mint to under the master mint authority
+  MintTo {
+    mint: Pubkey,
+    dst: Pubkey,
+    amount: u64,
+  },
+}
+impl Default for ProposalInstructionKind {
+  fn default() -> Self {
+    ProposalInstructionKind::Raw
+  }
+}
+impl ProposalInstructionKind {
+  pub fn data_len(&self) -> usize {
+    match self {
+      ProposalInstructionKind::Raw => 0,
+      ProposalInstructionKind::TokenTransfer { .. } => 10,
+      ProposalInstructionKind::SolTransfer { .. } => 12,
+      ProposalInstructionKind::MintTo { .. } => 10,
+    }
+  }
+
+  pub fn accounts_len(&self) -> usize {
+    match self {
+      ProposalInstructionKind::Raw => 0,
+      ProposalInstructionKind::TokenTransfer { .. } => 4,
+      ProposalInstructionKind::SolTransfer { .. } => 2,
+      ProposalInstructionKind::MintTo { .. } => 3,
+    }
+  }
+}
+
+#[account]
+pub struct ProposalInstruction {
+  pub proposal: Pubkey,
+  pub index: u8,
+  // Send all transaction
+  pub tx_index: u8,
+  pub executed: bool,
+  pub failed: bool,
+  // Data for the inter action
+  pub data_len: u64,
+  pub data: Vec<u8>,
+  // Accounts for the inter action
+  pub accounts_len: u8,
+  pub accounts: Vec<InvokedAccount>,
+  // Program to execute
+  pub invoked_program: Pubkey,
+  pub kind: ProposalInstructionKind,
+}
+
+impl ProposalInstruction {
+  pub const HEADER_LEN: usize = DISCRIMINATOR_SIZE
+    + U8_SIZE * 2
+    + PUBKEY_SIZE
+    + PUBKEY_SIZE
+    + U64_SIZE
+    + U8_SIZE
+    + BOOL_SIZE * 2
+    + PROPOSAL_INSTRUCTION_KIND_SIZE;
+
+  pub fn space(data_len: usize, accounts_len: usize) -> usize {
+    ProposalInstruction::HEADER_LEN
+      + VECTOR_OVERHEAD_SIZE
+      + data_len
+      + VECTOR_OVERHEAD_SIZE
+      + accounts_len * INVOKED_ACCOUNT_SIZE
+  }
+
+  pub fn is_executed(&self) -> bool {
+    self.executed
+  }
+
+  pub fn is_failed(&self) -> bool {
+    self.failed
+  }
+}
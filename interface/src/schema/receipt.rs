@@ -27,6 +27,8 @@ pub struct Receipt {
   pub locked_date: i64,
   pub unlocked_date: i64,
   pub action: ReceiptAction,
+  // The vote escrow backing the vote, or the default pubkey for deposited tokens
+  pub escrow: Pubkey,
 }
 
 impl Receipt {
@@ -39,5 +41,6 @@ impl Receipt {
     + U128_SIZE
     + I64_SIZE
     + I64_SIZE
-    + U8_SIZE;
+    + U8_SIZE
+    + PUBKEY_SIZE;
 }
@@ -0,0 +1,50 @@
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::schema::receipt::ReceiptAction;
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct VoterRecord {
+  pub authority: Pubkey,
+  pub proposal: Pubkey,
+  pub action: ReceiptAction,
+  // Deposit receipts are indexed from zero in the voting order
+  pub receipts: u64,
+}
+
+impl VoterRecord {
+  pub const LEN: usize = DISCRIMINATOR_SIZE + PUBKEY_SIZE + PUBKEY_SIZE + U8_SIZE + U64_SIZE;
+
+  ///
+  /// Pin the voting direction on the first vote and reject the opposite one afterwards
+  ///
+  pub fn record(
+    &mut self,
+    authority: Pubkey,
+    proposal: Pubkey,
+    action: ReceiptAction,
+  ) -> Result<()> {
+    if self.authority == Pubkey::default() {
+      self.authority = authority;
+      self.proposal = proposal;
+      self.action = action;
+    }
+    if self.action != action {
+      return err!(ErrorCode::ConflictingVoteDirection);
+    }
+    Ok(())
+  }
+
+  ///
+  /// Assign the next receipt index to the voter
+  ///
+  pub fn next_receipt(&mut self, authority: Pubkey, proposal: Pubkey) -> Result<u64> {
+    if self.authority == Pubkey::default() {
+      self.authority = authority;
+      self.proposal = proposal;
+    }
+    let index = self.receipts;
+    self.receipts = index.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    Ok(index)
+  }
+}
@@ -0,0 +1,77 @@
+use crate::schema::receipt::Receipt;
+use crate::utils::current_timestamp;
+use anchor_lang::prelude::*;
+
+///
+/// Permission for dao
+///
+pub trait Permission {
+  fn is_authorized_to_propose(&self, caller: Pubkey) -> bool;
+  fn is_authorized_to_execute(&self, caller: Pubkey) -> bool;
+  // Guardians are those snapshotted by the proposal
+  fn is_authorized_to_veto(&self, caller: Pubkey, guardians: &[Pubkey]) -> bool;
+  fn is_authorized_to_update(&self, caller: Pubkey) -> bool;
+}
+
+///
+/// Consensus for proposal
+///
+pub trait Consensus {
+  fn calculate_my_power_at(&self, amount: u64, now: i64, receipt: &mut Receipt) -> Option<u128>;
+  fn vote_for_at(&mut self, amount: u64, now: i64, receipt: &mut Receipt) -> Option<(u128, u128)>;
+  fn vote_against_at(
+    &mut self,
+    amount: u64,
+    now: i64,
+    receipt: &mut Receipt,
+  ) -> Option<(u128, u128)>;
+  fn increase_vote_at(
+    &mut self,
+    amount: u64,
+    now: i64,
+    receipt: &mut Receipt,
+  ) -> Option<(u128, u128)>;
+  fn is_consented(&self) -> bool;
+  fn is_decided(&self) -> bool;
+  // Thin wrappers reading the clock sysvar
+  fn calculate_my_power(&self, amount: u64, receipt: &mut Receipt) -> Option<u128> {
+    self.calculate_my_power_at(amount, current_timestamp()?, receipt)
+  }
+  fn vote_for(&mut self, amount: u64, receipt: &mut Receipt) -> Option<(u128, u128)> {
+    self.vote_for_at(amount, current_timestamp()?, receipt)
+  }
+  fn vote_against(&mut self, amount: u64, receipt: &mut Receipt) -> Option<(u128, u128)> {
+    self.vote_against_at(amount, current_timestamp()?, receipt)
+  }
+  fn increase_vote(&mut self, amount: u64, receipt: &mut Receipt) -> Option<(u128, u128)> {
+    self.increase_vote_at(amount, current_timestamp()?, receipt)
+  }
+}
+
+///
+/// Age for proposal
+///
+pub trait Age {
+  fn is_started_at(&self, now: i64) -> bool;
+  fn is_ended_at(&self, now: i64) -> bool;
+  fn is_timelocked_at(&self, now: i64) -> bool;
+  fn is_expired_at(&self, now: i64) -> bool;
+  fn is_retry_window_passed_at(&self, now: i64) -> bool;
+  fn is_executed(&self) -> bool;
+  // Thin wrappers reading the clock sysvar
+  fn is_started(&self) -> bool {
+    self.is_started_at(current_timestamp().unwrap_or(0))
+  }
+  fn is_ended(&self) -> bool {
+    self.is_ended_at(current_timestamp().unwrap_or(0))
+  }
+  fn is_timelocked(&self) -> bool {
+    self.is_timelocked_at(current_timestamp().unwrap_or(0))
+  }
+  fn is_expired(&self) -> bool {
+    self.is_expired_at(current_timestamp().unwrap_or(0))
+  }
+  fn is_retry_window_passed(&self) -> bool {
+    self.is_retry_window_passed_at(current_timestamp().unwrap_or(0))
+  }
+}
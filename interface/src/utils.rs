@@ -0,0 +1,32 @@
+use crate::constants::*;
+use crate::schema::metadata::HashAlgorithm;
+use anchor_lang::prelude::*;
+
+pub fn current_timestamp() -> Option<i64> {
+  let clock = Clock::get().ok()?;
+  Some(clock.unix_timestamp)
+}
+
+///
+/// Metadata URIs are optional and point to IPFS, Arweave or HTTPS.
+/// Content-addressed schemes must be hashed the way they are addressed,
+/// and mutable HTTPS hosts must come with a metadata hash to be checked against
+///
+pub fn is_valid_metadata(metadata: &[u8; 32], hash_algorithm: HashAlgorithm, uri: &str) -> bool {
+  if uri.len() > MAX_METADATA_URI_SIZE {
+    return false;
+  }
+  if uri.is_empty() {
+    return true;
+  }
+  if uri.starts_with("ipfs://") {
+    return matches!(
+      hash_algorithm,
+      HashAlgorithm::Sha256 | HashAlgorithm::Blake3
+    );
+  }
+  if uri.starts_with("ar://") {
+    return hash_algorithm == HashAlgorithm::Sha256;
+  }
+  uri.starts_with("https://") && metadata != &[0; 32]
+}
@@ -2,12 +2,11 @@ import {
   AnchorProvider,
   BN,
   Program,
-  SplToken,
   utils,
   Wallet,
   web3,
-} from '@project-serum/anchor'
-import { program as getSplProgram } from '@project-serum/anchor/dist/cjs/spl/token'
+} from '@coral-xyz/anchor'
+import { SplToken, splTokenProgram } from '@coral-xyz/spl-token'
 import { expect } from 'chai'
 // @ts-ignore
 import * as soproxABI from 'soprox-abi'
@@ -17,6 +16,8 @@ import InterDAO, {
   ConsensusQuorums,
   DaoRegimes,
   DEFAULT_INTERDAO_PROGRAM_ID,
+  HashAlgorithms,
+  ProposalStates,
 } from '../app'
 import { asyncWait, initializeAccount, initializeMint } from './pretest'
 
@@ -58,7 +59,7 @@ describe('@interdao/core', function () {
   before(async () => {
     const { program } = new InterDAO(wallet)
     const provider = program.provider as AnchorProvider
-    splProgram = getSplProgram(provider)
+    splProgram = splTokenProgram({ provider })
     // Init a token
     const token = web3.Keypair.generate()
     tokenAddress = token.publicKey.toBase58()
@@ -79,8 +80,8 @@ describe('@interdao/core', function () {
     await splProgram.rpc.mintTo(SUPPLY, {
       accounts: {
         mint: new web3.PublicKey(tokenAddress),
-        to: new web3.PublicKey(associatedTokenAddress),
-        authority: wallet.publicKey,
+        account: new web3.PublicKey(associatedTokenAddress),
+        owner: wallet.publicKey,
       },
     })
   })
@@ -104,6 +105,11 @@ describe('@interdao/core', function () {
       tokenAddress,
       CIRCULATED_SUPPLY,
       PRIMARY_DUMMY_METADATA,
+      web3.Keypair.generate(),
+      DaoRegimes.Dictatorial,
+      false,
+      true,
+      { minVotingDuration: new BN(10) },
     )
     daoAddress = _daoAddress
   })
@@ -124,11 +130,11 @@ describe('@interdao/core', function () {
     await splProgram.rpc.mintTo(SUPPLY, {
       accounts: {
         mint: new web3.PublicKey(tokenAddress),
-        to: vaultPublicKey,
-        authority: wallet.publicKey,
+        account: vaultPublicKey,
+        owner: wallet.publicKey,
       },
     })
-    const { amount } = (await splProgram.account.token.fetch(
+    const { amount } = (await splProgram.account.account.fetch(
       vaultPublicKey,
     )) as any
     expect(SUPPLY.eq(amount)).true
@@ -197,6 +203,14 @@ describe('@interdao/core', function () {
     expect(dao.toBase58()).to.equal(daoAddress)
   })
 
+  it('get proposal status', async () => {
+    const { state, isFinal } = await interDAO.getProposalStatus(
+      proposalAddress,
+    )
+    expect(state).to.deep.equal(ProposalStates.Draft)
+    expect(isFinal).false
+  })
+
   it('vote for', async () => {
     await asyncWait(30000) // Wait for 20s
     const { receiptAddress } = await interDAO.voteFor(
@@ -213,10 +227,7 @@ describe('@interdao/core', function () {
   })
 
   it('get receipt data after vote', async () => {
-    const nextIndex = await interDAO.findAvailableReceiptIndex(
-      proposalAddress,
-      wallet.publicKey.toBase58(),
-    )
+    const nextIndex = await interDAO.getNextReceiptIndex(proposalAddress)
     const expectedReceiptAddress = await interDAO.deriveReceiptAddress(
       nextIndex.sub(new BN(1)),
       proposalAddress,
@@ -242,10 +253,7 @@ describe('@interdao/core', function () {
   })
 
   it('get receipt data after vote', async () => {
-    const nextIndex = await interDAO.findAvailableReceiptIndex(
-      proposalAddress,
-      wallet.publicKey.toBase58(),
-    )
+    const nextIndex = await interDAO.getNextReceiptIndex(proposalAddress)
     const expectedReceiptAddress = await interDAO.deriveReceiptAddress(
       nextIndex.sub(new BN(1)),
       proposalAddress,
@@ -258,11 +266,11 @@ describe('@interdao/core', function () {
 
   it('execute the proposal', async () => {
     await asyncWait(30000) // Wait for 30s
-    const { amount: prevAmount } = await splProgram.account.token.fetch(
+    const { amount: prevAmount } = await splProgram.account.account.fetch(
       associatedTokenAddress,
     )
-    await interDAO.executeProposal(proposalAddress)
-    const { amount: nextAmount } = await splProgram.account.token.fetch(
+    await interDAO.executeProposal({ proposal: proposalAddress })
+    const { amount: nextAmount } = await splProgram.account.account.fetch(
       associatedTokenAddress,
     )
     expect(nextAmount.sub(prevAmount).eq(TRANSFERRED_AMOUNT)).true
@@ -270,10 +278,19 @@ describe('@interdao/core', function () {
     expect(executed).true
   })
 
+  it('finalize the proposal', async () => {
+    await interDAO.finalizeProposal(proposalAddress)
+    const { state } = await interDAO.getProposalData(proposalAddress)
+    expect(state).to.deep.equal(ProposalStates.Executed)
+  })
+
   it('close all receipts', async () => {
+    // Closability follows the on-chain state rather than the end date
+    const { isFinal } = await interDAO.getProposalStatus(proposalAddress)
+    expect(isFinal).true
     await interDAO.close(voteForReceiptAddress)
     await interDAO.close(voteAgainstReceiptAddress)
-    const { amount } = await splProgram.account.token.fetch(
+    const { amount } = await splProgram.account.account.fetch(
       associatedTokenAddress,
     )
     expect(SUPPLY.add(TRANSFERRED_AMOUNT).eq(amount)).true
@@ -293,15 +310,31 @@ describe('@interdao/core', function () {
   })
 
   it('update DAO metadata', async () => {
-    await interDAO.updateDaoMetadata(SECONDARY_DUMMY_METADATA, daoAddress)
-    const { metadata } = await interDAO.getDaoData(daoAddress)
+    const uri = 'ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi'
+    await interDAO.updateDaoMetadata(
+      SECONDARY_DUMMY_METADATA,
+      daoAddress,
+      HashAlgorithms.Sha256,
+      uri,
+    )
+    const { metadata, metadataUri } = await interDAO.getDaoData(daoAddress)
     expect(metadata).deep.equal(SECONDARY_DUMMY_METADATA.toJSON().data)
+    expect(metadataUri).to.equal(uri)
   })
 
   it('transfer authority', async () => {
     const newAuthorityAddress = web3.Keypair.generate().publicKey.toBase58()
     await interDAO.transferAuthority(newAuthorityAddress, daoAddress)
-    const { authority } = await interDAO.getDaoData(daoAddress)
-    expect(authority.toBase58()).to.equal(newAuthorityAddress)
+    const { authority, pendingAuthority } = await interDAO.getDaoData(
+      daoAddress,
+    )
+    expect(authority.toBase58()).to.equal(wallet.publicKey.toBase58())
+    expect(pendingAuthority.toBase58()).to.equal(newAuthorityAddress)
+  })
+
+  it('cancel the authority transfer', async () => {
+    await interDAO.cancelAuthorityTransfer(daoAddress)
+    const { pendingAuthority } = await interDAO.getDaoData(daoAddress)
+    expect(pendingAuthority.equals(web3.PublicKey.default)).true
   })
 })
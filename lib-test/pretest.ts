@@ -1,10 +1,5 @@
-import {
-  AnchorProvider,
-  Program,
-  SplToken,
-  utils,
-  web3,
-} from '@project-serum/anchor'
+import { AnchorProvider, Program, utils, web3 } from '@coral-xyz/anchor'
+import { SplToken } from '@coral-xyz/spl-token'
 
 export const initializeMint = async (
   decimals: number,
@@ -0,0 +1,134 @@
+use crate::{
+  Content, Dao, Escrow, Proposal, ProposalInstruction, ProposalStatus, Receipt, VoterRecord,
+};
+use anchor_lang::{prelude::*, AccountDeserialize, Discriminator};
+use std::convert::TryInto;
+
+///
+/// Any account owned by the program
+///
+#[derive(Clone)]
+pub enum InterDaoAccount {
+  Dao(Box<Dao>),
+  Proposal(Box<Proposal>),
+  ProposalInstruction(Box<ProposalInstruction>),
+  Receipt(Box<Receipt>),
+  Content(Box<Content>),
+  Escrow(Box<Escrow>),
+  VoterRecord(Box<VoterRecord>),
+}
+
+impl InterDaoAccount {
+  ///
+  /// Decode the account data by its discriminator
+  ///
+  pub fn decode(data: &[u8]) -> Result<Self> {
+    if data.len() < 8 {
+      return err!(ErrorCode::AccountDiscriminatorNotFound);
+    }
+    let discriminator: [u8; 8] = data[..8].try_into().unwrap();
+    let account = match discriminator {
+      d if d == Dao::discriminator() => Self::Dao(Box::new(decode(data)?)),
+      d if d == Proposal::discriminator() => Self::Proposal(Box::new(decode(data)?)),
+      d if d == ProposalInstruction::discriminator() => {
+        Self::ProposalInstruction(Box::new(decode(data)?))
+      }
+      d if d == Receipt::discriminator() => Self::Receipt(Box::new(decode(data)?)),
+      d if d == Content::discriminator() => Self::Content(Box::new(decode(data)?)),
+      d if d == Escrow::discriminator() => Self::Escrow(Box::new(decode(data)?)),
+      d if d == VoterRecord::discriminator() => Self::VoterRecord(Box::new(decode(data)?)),
+      _ => return err!(ErrorCode::AccountDiscriminatorMismatch),
+    };
+    Ok(account)
+  }
+}
+
+///
+/// Decode the account data, checking the discriminator
+///
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+  let mut data = data;
+  T::try_deserialize(&mut data)
+}
+
+pub fn decode_dao(data: &[u8]) -> Result<Dao> {
+  decode(data)
+}
+
+pub fn decode_proposal(data: &[u8]) -> Result<Proposal> {
+  decode(data)
+}
+
+pub fn decode_proposal_instruction(data: &[u8]) -> Result<ProposalInstruction> {
+  decode(data)
+}
+
+pub fn decode_receipt(data: &[u8]) -> Result<Receipt> {
+  decode(data)
+}
+
+pub fn decode_content(data: &[u8]) -> Result<Content> {
+  decode(data)
+}
+
+pub fn decode_escrow(data: &[u8]) -> Result<Escrow> {
+  decode(data)
+}
+
+pub fn decode_voter_record(data: &[u8]) -> Result<VoterRecord> {
+  decode(data)
+}
+
+///
+/// Decode the return data of `get_proposal_status`
+///
+pub fn decode_proposal_status(return_data: &[u8]) -> Result<ProposalStatus> {
+  ProposalStatus::try_from_slice(return_data).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HashAlgorithm;
+  use anchor_lang::AccountSerialize;
+
+  fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    data
+  }
+
+  #[test]
+  fn test_decode_by_discriminator() {
+    let authority = Pubkey::new_unique();
+    let data = serialize(&Content {
+      index: 0,
+      authority,
+      discriminator: [1; 8],
+      metadata: [2; 32],
+      dao: Pubkey::new_unique(),
+      proposal: Pubkey::default(),
+      parent: Pubkey::default(),
+      created_at: 0,
+      updated_at: 0,
+      revision: 0,
+      hash_algorithm: HashAlgorithm::Sha256,
+      metadata_uri: "ipfs://bafy".to_string(),
+    });
+    match InterDaoAccount::decode(&data).unwrap() {
+      InterDaoAccount::Content(content) => {
+        assert_eq!(content.authority, authority);
+        assert_eq!(content.metadata_uri, "ipfs://bafy");
+      }
+      _ => panic!("expected a content account"),
+    }
+    assert!(decode_content(&data).is_ok());
+    assert!(decode_receipt(&data).is_err());
+  }
+
+  #[test]
+  fn test_decode_unknown_account() {
+    assert!(InterDaoAccount::decode(&[0; 4]).is_err());
+    assert!(InterDaoAccount::decode(&[0; 64]).is_err());
+  }
+}
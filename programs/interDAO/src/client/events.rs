@@ -0,0 +1,195 @@
+use anchor_lang::{prelude::*, Discriminator};
+use std::{convert::TryInto, fmt, str::FromStr};
+
+const PROGRAM_LOG: &str = "Program log: ";
+const PROGRAM_DATA: &str = "Program data: ";
+const PROGRAM_RETURN: &str = "Program return: ";
+
+///
+/// Errors while decoding the events of a transaction
+///
+#[derive(Debug)]
+pub enum DecodeError {
+  Base64(base64::DecodeError),
+  Data(std::io::Error),
+}
+
+impl fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DecodeError::Base64(err) => write!(f, "invalid event encoding: {}", err),
+      DecodeError::Data(err) => write!(f, "invalid event data: {}", err),
+    }
+  }
+}
+
+impl std::error::Error for DecodeError {}
+
+macro_rules! events {
+  ($($variant:ident => $event:ident),* $(,)?) => {
+    ///
+    /// Any event emitted by the program
+    ///
+    pub enum InterDaoEvent {
+      $($variant(crate::$event),)*
+    }
+
+    impl InterDaoEvent {
+      ///
+      /// Decode an event by its discriminator. Unknown discriminators yield `None`
+      ///
+      pub fn decode(data: &[u8]) -> std::result::Result<Option<Self>, DecodeError> {
+        if data.len() < 8 {
+          return Ok(None);
+        }
+        let (discriminator, mut data) = data.split_at(8);
+        let discriminator: [u8; 8] = discriminator.try_into().unwrap();
+        $(
+          if discriminator == crate::$event::discriminator() {
+            let event = crate::$event::deserialize(&mut data).map_err(DecodeError::Data)?;
+            return Ok(Some(InterDaoEvent::$variant(event)));
+          }
+        )*
+        Ok(None)
+      }
+
+      pub fn name(&self) -> &'static str {
+        match self {
+          $(InterDaoEvent::$variant(_) => stringify!($event),)*
+        }
+      }
+    }
+  };
+}
+
+events! {
+  InitializeDao => InitializeDAOEvent,
+  InitializeProposal => InitializeProposalEvent,
+  InitializeProposalInstruction => InitializeProposalInstructionEvent,
+  InitializeProposalTemplate => InitializeProposalTemplateEvent,
+  AmendProposal => AmendProposalEvent,
+  ReplaceProposalInstruction => ReplaceProposalInstructionEvent,
+  RemoveProposalInstruction => RemoveProposalInstructionEvent,
+  VoteFor => VoteForEvent,
+  VoteNftFor => VoteNftForEvent,
+  VoteAgainst => VoteAgainstEvent,
+  VoteNftAgainst => VoteNftAgainstEvent,
+  IncreaseVote => IncreaseVoteEvent,
+  LockEscrow => LockEscrowEvent,
+  WithdrawEscrow => WithdrawEscrowEvent,
+  VoteEscrowFor => VoteEscrowForEvent,
+  VoteEscrowAgainst => VoteEscrowAgainstEvent,
+  ExecuteProposalInstruction => ExecuteProposalInstructionEvent,
+  FailProposalInstruction => FailProposalInstructionEvent,
+  FinalizeProposal => FinalizeProposalEvent,
+  Close => CloseEvent,
+  CloseNftVoting => CloseNftVotingEvent,
+  CloseEscrowVoting => CloseEscrowVotingEvent,
+  UpdateDaoRegime => UpdateDaoRegimeEvent,
+  UpdateDaoMetadata => UpdateDaoMetadataEvent,
+  UpdateSupply => UpdateSupplyEvent,
+  TransferAuthority => TransferAuthorityEvent,
+  AcceptAuthority => AcceptAuthorityEvent,
+  CancelAuthorityTransfer => CancelAuthorityTransferEvent,
+  RenounceAuthority => RenounceAuthorityEvent,
+  UpdateProposalBounds => UpdateProposalBoundsEvent,
+  UpdateEarlyFinalization => UpdateEarlyFinalizationEvent,
+  UpdateOneDirectionVoting => UpdateOneDirectionVotingEvent,
+  UpdateSnapshotVoting => UpdateSnapshotVotingEvent,
+  UpdateDaoGuardians => UpdateDaoGuardiansEvent,
+  UpdateDaoCouncil => UpdateDaoCouncilEvent,
+  VetoProposal => VetoProposalEvent,
+  InitializeContent => InitializeContentEvent,
+  Content => ContentEvent,
+  DeleteContent => DeleteContentEvent,
+  UpdateContentThreshold => UpdateContentThresholdEvent,
+}
+
+///
+/// Decode the events of a transaction from its log messages.
+/// Only the data logged while the program itself is running is considered,
+/// so events of other programs and of programs invoked by this one are skipped.
+/// Events of a failed instruction are dropped, since its changes are rolled back.
+/// Lines written by programs never move the stack, so they can't spoof an invocation.
+///
+pub fn parse_logs<S: AsRef<str>>(
+  logs: &[S],
+) -> std::result::Result<Vec<InterDaoEvent>, DecodeError> {
+  let program_id = crate::ID.to_string();
+  let mut stack: Vec<&str> = vec![];
+  let mut pending = vec![];
+  let mut events = vec![];
+  for log in logs.iter().map(AsRef::as_ref) {
+    if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+      if stack.last() != Some(&program_id.as_str()) {
+        continue;
+      }
+      for field in data.split_whitespace() {
+        let data = base64::decode(field).map_err(DecodeError::Base64)?;
+        if let Some(event) = InterDaoEvent::decode(&data)? {
+          pending.push(event);
+        }
+      }
+    } else if log.starts_with(PROGRAM_LOG) || log.starts_with(PROGRAM_RETURN) {
+      continue;
+    } else if let Some((program, status)) = log
+      .strip_prefix("Program ")
+      .and_then(|log| log.split_once(' '))
+      .filter(|(program, _)| Pubkey::from_str(program).is_ok())
+    {
+      // Other lines, like the consumed compute units, leave the stack as is
+      if status.starts_with("invoke [") {
+        stack.push(program);
+      } else if status.starts_with("failed") && stack.last() == Some(&program) {
+        stack.pop();
+        pending.clear();
+      } else if status == "success" && stack.last() == Some(&program) {
+        stack.pop();
+        if stack.is_empty() {
+          events.append(&mut pending);
+        }
+      }
+    }
+  }
+  Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::UpdateSupplyEvent;
+
+  fn program_data(event: &UpdateSupplyEvent) -> String {
+    let mut data = UpdateSupplyEvent::discriminator().to_vec();
+    event.serialize(&mut data).unwrap();
+    format!("{}{}", PROGRAM_DATA, base64::encode(data))
+  }
+
+  #[test]
+  fn test_spoofed_logs_leave_the_stack_as_is() {
+    let (program_id, invoked) = (crate::ID.to_string(), Pubkey::new_unique().to_string());
+    let event = UpdateSupplyEvent {
+      version: UpdateSupplyEvent::VERSION,
+      authority: Pubkey::new_unique(),
+      dao: Pubkey::new_unique(),
+      old_supply: 1,
+      new_supply: 2,
+    };
+    let logs = vec![
+      format!("Program {} invoke [1]", program_id),
+      format!("Program {} invoke [2]", invoked),
+      "Program log: invoke [1]".to_string(),
+      format!("Program log: {} invoke [1]", program_id),
+      format!("Program return: {} invoke [1]", invoked),
+      format!("Program {} success", invoked),
+      program_data(&event),
+      format!("Program {} success", program_id),
+    ];
+    let events = parse_logs(&logs).unwrap();
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+      InterDaoEvent::UpdateSupply(decoded) => assert_eq!(decoded.new_supply, 2),
+      _ => panic!("expected an update supply event"),
+    }
+  }
+}
@@ -0,0 +1,994 @@
+use crate::client::pda;
+use crate::{
+  accounts, instruction, ConsensusMechanism, ConsensusQuorum, DaoRegime, HashAlgorithm,
+  ProposalInstruction, ProposalInstructionKind,
+};
+use anchor_lang::{
+  prelude::*,
+  solana_program::{instruction::Instruction, system_program, sysvar},
+  InstructionData,
+};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+  Instruction {
+    program_id: crate::ID,
+    accounts: accounts.to_account_metas(None),
+    data: data.data(),
+  }
+}
+
+///
+/// Split an instruction into the arguments of a proposal instruction
+///
+struct InvokedInstruction {
+  data: Vec<u8>,
+  pubkeys: Vec<Pubkey>,
+  is_signers: Vec<bool>,
+  is_writables: Vec<bool>,
+  is_masters: Vec<bool>,
+}
+
+impl InvokedInstruction {
+  fn new(dao: &Pubkey, ix: &Instruction) -> Self {
+    let (master, _) = pda::master(dao);
+    InvokedInstruction {
+      data: ix.data.clone(),
+      pubkeys: ix.accounts.iter().map(|meta| meta.pubkey).collect(),
+      is_signers: ix.accounts.iter().map(|meta| meta.is_signer).collect(),
+      is_writables: ix.accounts.iter().map(|meta| meta.is_writable).collect(),
+      is_masters: ix
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey == master)
+        .collect(),
+    }
+  }
+}
+
+pub fn initialize_dao(
+  authority: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  regime: DaoRegime,
+  supply: u64,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+  is_nft: bool,
+  is_public: bool,
+  min_voting_duration: i64,
+  max_voting_duration: i64,
+  min_notice_period: i64,
+  execution_window: i64,
+  retry_window: i64,
+) -> Instruction {
+  build(
+    accounts::InitializeDAO {
+      authority,
+      dao,
+      master: pda::master(&dao).0,
+      mint,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::InitializeDao {
+      regime,
+      supply,
+      metadata,
+      hash_algorithm,
+      metadata_uri,
+      is_nft,
+      is_public,
+      min_voting_duration,
+      max_voting_duration,
+      min_notice_period,
+      execution_window,
+      retry_window,
+    },
+  )
+}
+
+///
+/// The proposal address is derived from the current DAO nonce
+///
+pub fn initialize_proposal(
+  caller: Pubkey,
+  dao: Pubkey,
+  nonce: u64,
+  taxman: Pubkey,
+  revenueman: Pubkey,
+  consensus_mechanism: ConsensusMechanism,
+  consesus_quorum: ConsensusQuorum,
+  start_date: i64,
+  end_date: i64,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+  independent_instructions: bool,
+  tax: u64,
+  revenue: u64,
+) -> Instruction {
+  build(
+    accounts::InitializeProposal {
+      caller,
+      proposal: pda::proposal(&dao, nonce).0,
+      dao,
+      taxman,
+      revenueman,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::InitializeProposal {
+      consensus_mechanism,
+      consesus_quorum,
+      start_date,
+      end_date,
+      metadata,
+      hash_algorithm,
+      metadata_uri,
+      independent_instructions,
+      tax,
+      revenue,
+    },
+  )
+}
+
+///
+/// Accounts of `ix` matching the DAO master are flagged to be signed by the master
+///
+pub fn initialize_proposal_instruction(
+  caller: Pubkey,
+  proposal_instruction: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  ix: &Instruction,
+  tx_idx: u8,
+) -> Instruction {
+  let invoked = InvokedInstruction::new(&dao, ix);
+  build(
+    accounts::InitializeProposalInstruction {
+      caller,
+      proposal_instruction,
+      invoked_program: ix.program_id,
+      proposal,
+      dao,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::InitializeProposalInstruction {
+      data: invoked.data,
+      pubkeys: invoked.pubkeys,
+      is_signers: invoked.is_signers,
+      is_writables: invoked.is_writables,
+      is_masters: invoked.is_masters,
+      tx_idx,
+    },
+  )
+}
+
+///
+/// Token templates require the token program owning the mint
+///
+pub fn initialize_proposal_template(
+  caller: Pubkey,
+  proposal_instruction: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  token_program: Option<Pubkey>,
+  tx_idx: u8,
+  kind: ProposalInstructionKind,
+) -> Instruction {
+  let mint = match kind {
+    ProposalInstructionKind::TokenTransfer { mint, .. } => Some(mint),
+    ProposalInstructionKind::MintTo { mint, .. } => Some(mint),
+    _ => None,
+  };
+  build(
+    accounts::InitializeProposalTemplate {
+      caller,
+      proposal_instruction,
+      proposal,
+      dao,
+      master: pda::master(&dao).0,
+      mint,
+      token_program,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::InitializeProposalTemplate { tx_idx, kind },
+  )
+}
+
+pub fn amend_proposal(
+  caller: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  consensus_mechanism: ConsensusMechanism,
+  consesus_quorum: ConsensusQuorum,
+  start_date: i64,
+  end_date: i64,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+) -> Instruction {
+  build(
+    accounts::AmendProposal {
+      caller,
+      proposal,
+      dao,
+      system_program: system_program::ID,
+    },
+    instruction::AmendProposal {
+      consensus_mechanism,
+      consesus_quorum,
+      start_date,
+      end_date,
+      metadata,
+      hash_algorithm,
+      metadata_uri,
+    },
+  )
+}
+
+pub fn replace_proposal_instruction(
+  caller: Pubkey,
+  proposal_instruction: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  ix: &Instruction,
+  tx_idx: u8,
+) -> Instruction {
+  let invoked = InvokedInstruction::new(&dao, ix);
+  build(
+    accounts::ReplaceProposalInstruction {
+      caller,
+      proposal_instruction,
+      invoked_program: ix.program_id,
+      proposal,
+      dao,
+      system_program: system_program::ID,
+    },
+    instruction::ReplaceProposalInstruction {
+      data: invoked.data,
+      pubkeys: invoked.pubkeys,
+      is_signers: invoked.is_signers,
+      is_writables: invoked.is_writables,
+      is_masters: invoked.is_masters,
+      tx_idx,
+    },
+  )
+}
+
+///
+/// `following` are the proposal instructions after the removed one, in index order.
+/// They are re-indexed to keep the indices contiguous
+///
+pub fn remove_proposal_instruction(
+  caller: Pubkey,
+  proposal_instruction: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  following: &[Pubkey],
+) -> Instruction {
+  let mut ix = build(
+    accounts::RemoveProposalInstruction {
+      caller,
+      proposal_instruction,
+      proposal,
+      dao,
+    },
+    instruction::RemoveProposalInstruction {},
+  );
+  ix.accounts.extend(
+    following
+      .iter()
+      .map(|pubkey| AccountMeta::new(*pubkey, false)),
+  );
+  ix
+}
+
+///
+/// Votes are deposited from the associated token account of the authority.
+/// `receipts` is the receipt counter of the voter record, or zero before the first vote
+///
+pub fn vote_for(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  token_program: Pubkey,
+  taxman: Pubkey,
+  revenueman: Pubkey,
+  receipts: u64,
+  amount: u64,
+  tax: u64,
+  revenue: u64,
+) -> Instruction {
+  build(
+    accounts::VoteFor {
+      authority,
+      src: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      treasurer: pda::treasurer(&proposal).0,
+      mint,
+      treasury: pda::treasury(&proposal, &mint, &token_program),
+      proposal,
+      dao,
+      receipt: pda::receipt(&proposal, &authority, receipts).0,
+      voter_record: pda::voter_record(&proposal, &authority).0,
+      taxman,
+      revenueman,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::VoteFor {
+      amount,
+      tax,
+      revenue,
+    },
+  )
+}
+
+pub fn vote_nft_for(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  metadata: Pubkey,
+  token_program: Pubkey,
+  taxman: Pubkey,
+  revenueman: Pubkey,
+  receipts: u64,
+  tax: u64,
+  revenue: u64,
+) -> Instruction {
+  build(
+    accounts::VoteNftFor {
+      authority,
+      src: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      treasurer: pda::treasurer(&proposal).0,
+      mint,
+      metadata,
+      treasury: pda::treasury(&proposal, &mint, &token_program),
+      proposal,
+      dao,
+      receipt: pda::receipt(&proposal, &authority, receipts).0,
+      voter_record: pda::voter_record(&proposal, &authority).0,
+      taxman,
+      revenueman,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::VoteNftFor { tax, revenue },
+  )
+}
+
+pub fn vote_against(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  token_program: Pubkey,
+  taxman: Pubkey,
+  revenueman: Pubkey,
+  receipts: u64,
+  amount: u64,
+  tax: u64,
+  revenue: u64,
+) -> Instruction {
+  build(
+    accounts::VoteAgainst {
+      authority,
+      src: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      treasurer: pda::treasurer(&proposal).0,
+      mint,
+      treasury: pda::treasury(&proposal, &mint, &token_program),
+      proposal,
+      dao,
+      receipt: pda::receipt(&proposal, &authority, receipts).0,
+      voter_record: pda::voter_record(&proposal, &authority).0,
+      taxman,
+      revenueman,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::VoteAgainst {
+      amount,
+      tax,
+      revenue,
+    },
+  )
+}
+
+pub fn vote_nft_against(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  metadata: Pubkey,
+  token_program: Pubkey,
+  taxman: Pubkey,
+  revenueman: Pubkey,
+  receipts: u64,
+  tax: u64,
+  revenue: u64,
+) -> Instruction {
+  build(
+    accounts::VoteNftAgainst {
+      authority,
+      src: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      treasurer: pda::treasurer(&proposal).0,
+      mint,
+      metadata,
+      treasury: pda::treasury(&proposal, &mint, &token_program),
+      proposal,
+      dao,
+      receipt: pda::receipt(&proposal, &authority, receipts).0,
+      voter_record: pda::voter_record(&proposal, &authority).0,
+      taxman,
+      revenueman,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::VoteNftAgainst { tax, revenue },
+  )
+}
+
+pub fn increase_vote(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  token_program: Pubkey,
+  taxman: Pubkey,
+  revenueman: Pubkey,
+  index: u64,
+  amount: u64,
+  tax: u64,
+  revenue: u64,
+) -> Instruction {
+  build(
+    accounts::IncreaseVote {
+      authority,
+      src: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      treasurer: pda::treasurer(&proposal).0,
+      mint,
+      treasury: pda::treasury(&proposal, &mint, &token_program),
+      proposal,
+      dao,
+      receipt: pda::receipt(&proposal, &authority, index).0,
+      taxman,
+      revenueman,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::IncreaseVote {
+      amount,
+      tax,
+      revenue,
+    },
+  )
+}
+
+pub fn lock_escrow(
+  authority: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  token_program: Pubkey,
+  amount: u64,
+  unlocked_date: i64,
+) -> Instruction {
+  build(
+    accounts::LockEscrow {
+      authority,
+      src: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      escrow_treasurer: pda::escrow_treasurer(&dao).0,
+      mint,
+      escrow_treasury: pda::escrow_treasury(&dao, &mint, &token_program),
+      dao,
+      escrow: pda::escrow(&dao, &mint, &authority).0,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::LockEscrow {
+      amount,
+      unlocked_date,
+    },
+  )
+}
+
+pub fn withdraw_escrow(
+  authority: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  token_program: Pubkey,
+) -> Instruction {
+  build(
+    accounts::WithdrawEscrow {
+      authority,
+      dst: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      escrow_treasurer: pda::escrow_treasurer(&dao).0,
+      mint,
+      escrow_treasury: pda::escrow_treasury(&dao, &mint, &token_program),
+      dao,
+      escrow: pda::escrow(&dao, &mint, &authority).0,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::WithdrawEscrow {},
+  )
+}
+
+pub fn vote_escrow_for(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  taxman: Pubkey,
+  revenueman: Pubkey,
+  tax: u64,
+  revenue: u64,
+) -> Instruction {
+  let (escrow, _) = pda::escrow(&dao, &mint, &authority);
+  build(
+    accounts::VoteEscrowFor {
+      authority,
+      escrow,
+      proposal,
+      dao,
+      receipt: pda::escrow_receipt(&proposal, &escrow).0,
+      voter_record: pda::voter_record(&proposal, &authority).0,
+      taxman,
+      revenueman,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::VoteEscrowFor { tax, revenue },
+  )
+}
+
+pub fn vote_escrow_against(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  taxman: Pubkey,
+  revenueman: Pubkey,
+  tax: u64,
+  revenue: u64,
+) -> Instruction {
+  let (escrow, _) = pda::escrow(&dao, &mint, &authority);
+  build(
+    accounts::VoteEscrowAgainst {
+      authority,
+      escrow,
+      proposal,
+      dao,
+      receipt: pda::escrow_receipt(&proposal, &escrow).0,
+      voter_record: pda::voter_record(&proposal, &authority).0,
+      taxman,
+      revenueman,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::VoteEscrowAgainst { tax, revenue },
+  )
+}
+
+///
+/// The invoked accounts are appended from the stored proposal instruction.
+/// The master signs through the program, so no invoked account is passed as a signer
+///
+pub fn execute_proposal_instruction(
+  caller: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  proposal_instruction: Pubkey,
+  stored: &ProposalInstruction,
+) -> Instruction {
+  let mut ix = build(
+    accounts::ExecuteProposalInstruction {
+      caller,
+      proposal,
+      proposal_instruction,
+      dao,
+      master: pda::master(&dao).0,
+      invoked_program: stored.invoked_program,
+    },
+    instruction::ExecuteProposalInstruction {},
+  );
+  ix.accounts.extend(stored.accounts.iter().map(|acc| {
+    if acc.is_writable {
+      AccountMeta::new(acc.pubkey, false)
+    } else {
+      AccountMeta::new_readonly(acc.pubkey, false)
+    }
+  }));
+  ix
+}
+
+pub fn fail_proposal_instruction(
+  caller: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  proposal_instruction: Pubkey,
+) -> Instruction {
+  build(
+    accounts::FailProposalInstruction {
+      caller,
+      proposal,
+      proposal_instruction,
+      dao,
+    },
+    instruction::FailProposalInstruction {},
+  )
+}
+
+pub fn finalize_proposal(caller: Pubkey, proposal: Pubkey, dao: Pubkey) -> Instruction {
+  build(
+    accounts::FinalizeProposal {
+      caller,
+      proposal,
+      dao,
+    },
+    instruction::FinalizeProposal {},
+  )
+}
+
+///
+/// Tokens are returned to the associated token account of the authority
+///
+pub fn close(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  token_program: Pubkey,
+  index: u64,
+) -> Instruction {
+  build(
+    accounts::Close {
+      authority,
+      dst: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      treasurer: pda::treasurer(&proposal).0,
+      mint,
+      treasury: pda::treasury(&proposal, &mint, &token_program),
+      proposal,
+      dao,
+      receipt: pda::receipt(&proposal, &authority, index).0,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::Close {},
+  )
+}
+
+pub fn close_nft_voting(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+  metadata: Pubkey,
+  token_program: Pubkey,
+  index: u64,
+) -> Instruction {
+  build(
+    accounts::CloseNftVoting {
+      authority,
+      dst: get_associated_token_address_with_program_id(&authority, &mint, &token_program),
+      treasurer: pda::treasurer(&proposal).0,
+      mint,
+      metadata,
+      treasury: pda::treasury(&proposal, &mint, &token_program),
+      proposal,
+      dao,
+      receipt: pda::receipt(&proposal, &authority, index).0,
+      token_program,
+      associated_token_program: associated_token::ID,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    },
+    instruction::CloseNftVoting {},
+  )
+}
+
+///
+/// Escrow receipts hold no tokens. Closing them only reclaims the rent
+///
+pub fn close_escrow_voting(
+  authority: Pubkey,
+  proposal: Pubkey,
+  dao: Pubkey,
+  mint: Pubkey,
+) -> Instruction {
+  let (escrow, _) = pda::escrow(&dao, &mint, &authority);
+  build(
+    accounts::CloseEscrowVoting {
+      authority,
+      proposal,
+      dao,
+      receipt: pda::escrow_receipt(&proposal, &escrow).0,
+    },
+    instruction::CloseEscrowVoting {},
+  )
+}
+
+pub fn update_dao_regime(authority: Pubkey, dao: Pubkey, regime: DaoRegime) -> Instruction {
+  build(
+    accounts::UpdateDaoRegime { authority, dao },
+    instruction::UpdateDaoRegime { regime },
+  )
+}
+
+///
+/// The DAO account is resized to the metadata URI, the authority pays or is refunded the rent
+///
+pub fn update_dao_metadata(
+  authority: Pubkey,
+  dao: Pubkey,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+) -> Instruction {
+  build(
+    accounts::UpdateDaoMetadata {
+      authority,
+      dao,
+      system_program: system_program::ID,
+    },
+    instruction::UpdateDaoMetadata {
+      metadata,
+      hash_algorithm,
+      metadata_uri,
+    },
+  )
+}
+
+pub fn update_supply(authority: Pubkey, dao: Pubkey, supply: u64) -> Instruction {
+  build(
+    accounts::UpdateSupply { authority, dao },
+    instruction::UpdateSupply { supply },
+  )
+}
+
+pub fn transfer_authority(authority: Pubkey, dao: Pubkey, new_authority: Pubkey) -> Instruction {
+  build(
+    accounts::TransferAuthority {
+      authority,
+      new_authority,
+      dao,
+    },
+    instruction::TransferAuthority {},
+  )
+}
+
+pub fn accept_authority(new_authority: Pubkey, dao: Pubkey) -> Instruction {
+  build(
+    accounts::AcceptAuthority { new_authority, dao },
+    instruction::AcceptAuthority {},
+  )
+}
+
+pub fn cancel_authority_transfer(authority: Pubkey, dao: Pubkey) -> Instruction {
+  build(
+    accounts::CancelAuthorityTransfer { authority, dao },
+    instruction::CancelAuthorityTransfer {},
+  )
+}
+
+pub fn renounce_authority(authority: Pubkey, dao: Pubkey) -> Instruction {
+  build(
+    accounts::RenounceAuthority { authority, dao },
+    instruction::RenounceAuthority {},
+  )
+}
+
+pub fn update_proposal_bounds(
+  authority: Pubkey,
+  dao: Pubkey,
+  min_voting_duration: i64,
+  max_voting_duration: i64,
+  min_notice_period: i64,
+  execution_window: i64,
+  retry_window: i64,
+) -> Instruction {
+  build(
+    accounts::UpdateProposalBounds { authority, dao },
+    instruction::UpdateProposalBounds {
+      min_voting_duration,
+      max_voting_duration,
+      min_notice_period,
+      execution_window,
+      retry_window,
+    },
+  )
+}
+
+pub fn update_early_finalization(
+  authority: Pubkey,
+  dao: Pubkey,
+  early_finalization: bool,
+) -> Instruction {
+  build(
+    accounts::UpdateEarlyFinalization { authority, dao },
+    instruction::UpdateEarlyFinalization { early_finalization },
+  )
+}
+
+pub fn update_one_direction_voting(
+  authority: Pubkey,
+  dao: Pubkey,
+  one_direction_voting: bool,
+) -> Instruction {
+  build(
+    accounts::UpdateOneDirectionVoting { authority, dao },
+    instruction::UpdateOneDirectionVoting {
+      one_direction_voting,
+    },
+  )
+}
+
+pub fn update_snapshot_voting(
+  authority: Pubkey,
+  dao: Pubkey,
+  snapshot_voting: bool,
+) -> Instruction {
+  build(
+    accounts::UpdateSnapshotVoting { authority, dao },
+    instruction::UpdateSnapshotVoting { snapshot_voting },
+  )
+}
+
+pub fn update_dao_guardians(
+  authority: Pubkey,
+  dao: Pubkey,
+  guardians: Vec<Pubkey>,
+  veto_threshold: u8,
+  timelock: i64,
+) -> Instruction {
+  build(
+    accounts::UpdateDaoGuardians { authority, dao },
+    instruction::UpdateDaoGuardians {
+      guardians,
+      veto_threshold,
+      timelock,
+    },
+  )
+}
+
+///
+/// Omit the council mint to turn the DAO back into a single house
+///
+pub fn update_dao_council(
+  authority: Pubkey,
+  dao: Pubkey,
+  council_mint: Option<Pubkey>,
+  council_supply: u64,
+) -> Instruction {
+  build(
+    accounts::UpdateDaoCouncil {
+      authority,
+      dao,
+      council_mint,
+    },
+    instruction::UpdateDaoCouncil { council_supply },
+  )
+}
+
+pub fn veto_proposal(caller: Pubkey, proposal: Pubkey, dao: Pubkey) -> Instruction {
+  build(
+    accounts::VetoProposal {
+      caller,
+      proposal,
+      dao,
+    },
+    instruction::VetoProposal {},
+  )
+}
+
+///
+/// The status is returned through the return data, see `accounts::decode_proposal_status`
+///
+pub fn get_proposal_status(proposal: Pubkey, dao: Pubkey) -> Instruction {
+  build(
+    accounts::GetProposalStatus { proposal, dao },
+    instruction::GetProposalStatus {},
+  )
+}
+
+///
+/// Omit the proposal to discuss the DAO itself, and the parent to start a new thread.
+/// `holdings` is the governance token account of the authority, required when the DAO gates content.
+/// `nonce` is the content nonce of the DAO. Existing content is updated through `edit_content`
+///
+pub fn initialize_content(
+  authority: Pubkey,
+  nonce: u64,
+  discriminator: [u8; 8],
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+  dao: Pubkey,
+  proposal: Option<Pubkey>,
+  parent: Option<Pubkey>,
+  holdings: Option<Pubkey>,
+) -> Instruction {
+  build(
+    accounts::InitializeContent {
+      authority,
+      content: pda::content(&dao, nonce).0,
+      dao,
+      proposal,
+      parent,
+      holdings,
+      system_program: system_program::ID,
+    },
+    instruction::InitializeContent {
+      discriminator,
+      metadata,
+      hash_algorithm,
+      metadata_uri,
+    },
+  )
+}
+
+///
+/// The content account is resized to the metadata URI, the authority pays or is refunded the rent
+///
+pub fn edit_content(
+  authority: Pubkey,
+  content: Pubkey,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+) -> Instruction {
+  build(
+    accounts::EditContent {
+      authority,
+      content,
+      system_program: system_program::ID,
+    },
+    instruction::EditContent {
+      metadata,
+      hash_algorithm,
+      metadata_uri,
+    },
+  )
+}
+
+///
+/// The rent is reclaimed by the authority
+///
+pub fn delete_content(authority: Pubkey, content: Pubkey) -> Instruction {
+  build(
+    accounts::DeleteContent { authority, content },
+    instruction::DeleteContent {},
+  )
+}
+
+pub fn update_content_threshold(
+  authority: Pubkey,
+  dao: Pubkey,
+  content_threshold: u64,
+) -> Instruction {
+  build(
+    accounts::UpdateContentThreshold { authority, dao },
+    instruction::UpdateContentThreshold { content_threshold },
+  )
+}
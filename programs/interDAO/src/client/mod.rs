@@ -0,0 +1,12 @@
+//!
+//! Off-chain helpers to interact with the interDAO program, enabled by the `client` feature
+//!
+//! - [`pda`] derives the program addresses from their seeds
+//! - [`instructions`] builds an instruction for every entry point of the program
+//! - [`accounts`] decodes the program accounts
+//! - [`events`] decodes the program events from transaction logs
+//!
+pub mod accounts;
+pub mod events;
+pub mod instructions;
+pub mod pda;
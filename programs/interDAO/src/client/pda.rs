@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+///
+/// The DAO master, signing the executed proposal instructions
+///
+pub fn master(dao: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[b"master", dao.as_ref()], &crate::ID)
+}
+
+///
+/// The proposal created with the DAO nonce
+///
+pub fn proposal(dao: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+  Pubkey::find_program_address(
+    &[b"proposal", &nonce.to_le_bytes(), dao.as_ref()],
+    &crate::ID,
+  )
+}
+
+///
+/// The owner of the proposal treasuries holding the deposited votes
+///
+pub fn treasurer(proposal: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[b"treasurer", proposal.as_ref()], &crate::ID)
+}
+
+///
+/// The proposal treasury of a mint
+///
+pub fn treasury(proposal: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+  let (treasurer, _) = treasurer(proposal);
+  get_associated_token_address_with_program_id(&treasurer, mint, token_program)
+}
+
+///
+/// The receipt of a deposited vote
+///
+pub fn receipt(proposal: &Pubkey, authority: &Pubkey, index: u64) -> (Pubkey, u8) {
+  Pubkey::find_program_address(
+    &[
+      b"receipt",
+      &index.to_le_bytes(),
+      proposal.as_ref(),
+      authority.as_ref(),
+    ],
+    &crate::ID,
+  )
+}
+
+///
+/// The deposit receipts of a voter, given the receipt counter of the voter record
+///
+pub fn receipts(proposal: &Pubkey, authority: &Pubkey, receipts: u64) -> Vec<Pubkey> {
+  (0..receipts)
+    .map(|index| receipt(proposal, authority, index).0)
+    .collect()
+}
+
+///
+/// The content posted at a DAO content nonce
+///
+pub fn content(dao: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+  Pubkey::find_program_address(
+    &[b"content", &nonce.to_le_bytes(), dao.as_ref()],
+    &crate::ID,
+  )
+}
+
+///
+/// The vote escrow of an authority for a governance mint
+///
+pub fn escrow(dao: &Pubkey, mint: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(
+    &[b"escrow", dao.as_ref(), mint.as_ref(), authority.as_ref()],
+    &crate::ID,
+  )
+}
+
+///
+/// The owner of the DAO escrow treasuries
+///
+pub fn escrow_treasurer(dao: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(&[b"escrow_treasurer", dao.as_ref()], &crate::ID)
+}
+
+///
+/// The DAO escrow treasury of a mint
+///
+pub fn escrow_treasury(dao: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+  let (escrow_treasurer, _) = escrow_treasurer(dao);
+  get_associated_token_address_with_program_id(&escrow_treasurer, mint, token_program)
+}
+
+///
+/// The receipt of a vote backed by an escrow
+///
+pub fn escrow_receipt(proposal: &Pubkey, escrow: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(
+    &[b"escrow_receipt", proposal.as_ref(), escrow.as_ref()],
+    &crate::ID,
+  )
+}
+
+///
+/// The receipt counter and voting direction of a voter on a proposal
+///
+pub fn voter_record(proposal: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+  Pubkey::find_program_address(
+    &[b"voter_record", proposal.as_ref(), authority.as_ref()],
+    &crate::ID,
+  )
+}
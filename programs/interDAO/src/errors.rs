@@ -0,0 +1 @@
+../../../interface/src/errors.rs
\ No newline at end of file
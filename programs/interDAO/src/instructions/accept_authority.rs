@@ -0,0 +1,41 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct AcceptAuthorityEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub new_authority: Pubkey,
+  pub dao: Pubkey,
+}
+
+impl AcceptAuthorityEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+  pub new_authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.pending_authority == new_authority.key() @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<AcceptAuthority>) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  let authority = dao.authority;
+  dao.authority = ctx.accounts.new_authority.key();
+  dao.pending_authority = Pubkey::default();
+
+  emit!(AcceptAuthorityEvent {
+    version: AcceptAuthorityEvent::VERSION,
+    authority,
+    new_authority: dao.authority,
+    dao: dao.key()
+  });
+
+  Ok(())
+}
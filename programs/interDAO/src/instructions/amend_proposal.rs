@@ -0,0 +1,120 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, metadata::*, proposal::*};
+use crate::traits::Age;
+use crate::utils::{current_timestamp, is_valid_metadata};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct AmendProposalEvent {
+  pub version: u8,
+  pub proposal: Pubkey,
+  pub dao: Pubkey,
+  pub caller: Pubkey,
+  pub old_consensus_mechanism: ConsensusMechanism,
+  pub new_consensus_mechanism: ConsensusMechanism,
+  pub old_consensus_quorum: ConsensusQuorum,
+  pub new_consensus_quorum: ConsensusQuorum,
+  pub old_start_date: i64,
+  pub new_start_date: i64,
+  pub old_end_date: i64,
+  pub new_end_date: i64,
+  pub old_metadata: [u8; 32],
+  pub new_metadata: [u8; 32],
+  pub old_hash_algorithm: HashAlgorithm,
+  pub new_hash_algorithm: HashAlgorithm,
+  pub old_metadata_uri: String,
+  pub new_metadata_uri: String,
+}
+
+impl AmendProposalEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+#[instruction(
+  consensus_mechanism: ConsensusMechanism,
+  consensus_quorum: ConsensusQuorum,
+  start_date: i64,
+  end_date: i64,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String
+)]
+pub struct AmendProposal<'info> {
+  #[account(mut)]
+  pub caller: Signer<'info>,
+  #[account(
+    mut,
+    realloc = Proposal::LEN + metadata_uri.len(),
+    realloc::payer = caller,
+    realloc::zero = false,
+    seeds = [
+      b"proposal".as_ref(),
+      &proposal.index.to_le_bytes(),
+      &dao.key().to_bytes()
+    ],
+    bump,
+    has_one = dao,
+    constraint = proposal.creator == caller.key() @ ErrorCode::NoPermission
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+  pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn exec(
+  ctx: Context<AmendProposal>,
+  consensus_mechanism: ConsensusMechanism,
+  consensus_quorum: ConsensusQuorum,
+  start_date: i64,
+  end_date: i64,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+) -> Result<()> {
+  let dao = &ctx.accounts.dao;
+  let proposal = &mut ctx.accounts.proposal;
+  // Validate proposal state
+  if proposal.is_started() {
+    return err!(ErrorCode::StartedProposal);
+  }
+  // Validate data
+  let now = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
+  dao.validate_proposal_dates(now, start_date, end_date)?;
+  if !is_valid_metadata(&metadata, hash_algorithm, &metadata_uri) {
+    return err!(ErrorCode::InvalidMetadataUri);
+  }
+
+  let event = AmendProposalEvent {
+    version: AmendProposalEvent::VERSION,
+    proposal: proposal.key(),
+    dao: proposal.dao,
+    caller: ctx.accounts.caller.key(),
+    old_consensus_mechanism: proposal.consensus_mechanism,
+    new_consensus_mechanism: consensus_mechanism,
+    old_consensus_quorum: proposal.consensus_quorum,
+    new_consensus_quorum: consensus_quorum,
+    old_start_date: proposal.start_date,
+    new_start_date: start_date,
+    old_end_date: proposal.end_date,
+    new_end_date: end_date,
+    old_metadata: proposal.metadata,
+    new_metadata: metadata,
+    old_hash_algorithm: proposal.hash_algorithm,
+    new_hash_algorithm: hash_algorithm,
+    old_metadata_uri: proposal.metadata_uri.clone(),
+    new_metadata_uri: metadata_uri.clone(),
+  };
+
+  proposal.consensus_mechanism = consensus_mechanism;
+  proposal.consensus_quorum = consensus_quorum;
+  proposal.start_date = start_date;
+  proposal.end_date = end_date;
+  proposal.metadata = metadata;
+  proposal.hash_algorithm = hash_algorithm;
+  proposal.metadata_uri = metadata_uri;
+
+  emit!(event);
+
+  Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CancelAuthorityTransferEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub pending_authority: Pubkey,
+  pub dao: Pubkey,
+}
+
+impl CancelAuthorityTransferEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  if dao.pending_authority == Pubkey::default() {
+    return err!(ErrorCode::NoPendingAuthority);
+  }
+  let pending_authority = dao.pending_authority;
+  dao.pending_authority = Pubkey::default();
+
+  emit!(CancelAuthorityTransferEvent {
+    version: CancelAuthorityTransferEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    pending_authority,
+    dao: dao.key()
+  });
+
+  Ok(())
+}
@@ -1,15 +1,23 @@
 use crate::errors::ErrorCode;
 use crate::schema::{dao::*, proposal::*, receipt::*};
-use crate::traits::Age;
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token, token};
+use anchor_spl::{associated_token, token_interface};
 
 #[event]
 pub struct CloseEvent {
+  pub version: u8,
   pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub proposal: Pubkey,
   pub receipt: Pubkey,
   pub mint: Pubkey,
+  pub action: ReceiptAction,
   pub amount: u64,
+  pub power: u128,
+}
+
+impl CloseEvent {
+  pub const VERSION: u8 = 1;
 }
 
 #[derive(Accounts)]
@@ -17,22 +25,30 @@ pub struct Close<'info> {
   #[account(mut)]
   pub authority: Signer<'info>,
   #[account(
-    init_if_needed,
-    payer = authority,
-    associated_token::mint = mint,
-    associated_token::authority = authority
+    mut,
+    address = associated_token::get_associated_token_address_with_program_id(
+      &authority.key(),
+      &mint.key(),
+      &token_program.key()
+    ) @ ErrorCode::InvalidAssociatedTokenAccount
   )]
-  pub dst: Box<Account<'info, token::TokenAccount>>,
+  /// CHECK: Created on demand as the associated token account of the authority
+  pub dst: AccountInfo<'info>,
   #[account(seeds = [b"treasurer".as_ref(), &proposal.key().to_bytes()], bump)]
   /// CHECK: Just a pure account
   pub treasurer: AccountInfo<'info>,
-  pub mint: Box<Account<'info, token::Mint>>,
+  pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
   #[account(
     mut,
-    associated_token::mint = mint,
-    associated_token::authority = treasurer
+    token::mint = mint,
+    token::authority = treasurer,
+    address = associated_token::get_associated_token_address_with_program_id(
+      &treasurer.key(),
+      &mint.key(),
+      &token_program.key()
+    ) @ ErrorCode::InvalidAssociatedTokenAccount
   )]
-  pub treasury: Box<Account<'info, token::TokenAccount>>,
+  pub treasury: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
   #[account(
     mut,
     seeds = [
@@ -44,7 +60,6 @@ pub struct Close<'info> {
     has_one = dao
   )]
   pub proposal: Account<'info, Proposal>,
-  #[account(has_one = mint)]
   pub dao: Account<'info, Dao>,
   #[account(
     mut,
@@ -57,24 +72,38 @@ pub struct Close<'info> {
     bump,
     has_one = authority,
     has_one = proposal,
+    has_one = mint,
   )]
   pub receipt: Account<'info, Receipt>,
-  pub token_program: Program<'info, token::Token>,
+  pub token_program: Interface<'info, token_interface::TokenInterface>,
   pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
   pub system_program: Program<'info, System>,
   pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn exec(ctx: Context<Close>) -> Result<()> {
+pub(crate) fn exec(ctx: Context<Close>) -> Result<()> {
   let receipt = &mut ctx.accounts.receipt;
-  let proposal = &ctx.accounts.proposal;
+  let proposal = &mut ctx.accounts.proposal;
   // Validate permission & consensus
-  if !proposal.is_ended() {
+  if let ProposalState::Draft | ProposalState::Voting = proposal.transition() {
     return err!(ErrorCode::NotEndedProposal);
   }
 
   let amount = receipt.amount;
   receipt.amount = 0;
+  // Create the destination on demand
+  let create_ctx = CpiContext::new(
+    ctx.accounts.associated_token_program.to_account_info(),
+    associated_token::Create {
+      payer: ctx.accounts.authority.to_account_info(),
+      associated_token: ctx.accounts.dst.to_account_info(),
+      authority: ctx.accounts.authority.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
+      system_program: ctx.accounts.system_program.to_account_info(),
+      token_program: ctx.accounts.token_program.to_account_info(),
+    },
+  );
+  associated_token::create_idempotent(create_ctx)?;
   // Unlock tokens out of the treasury
   let seeds: &[&[&[u8]]] = &[&[
     b"treasurer".as_ref(),
@@ -84,20 +113,26 @@ pub fn exec(ctx: Context<Close>) -> Result<()> {
 
   let transfer_ctx = CpiContext::new_with_signer(
     ctx.accounts.token_program.to_account_info(),
-    token::Transfer {
+    token_interface::TransferChecked {
       from: ctx.accounts.treasury.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
       to: ctx.accounts.dst.to_account_info(),
       authority: ctx.accounts.treasurer.to_account_info(),
     },
     seeds,
   );
-  token::transfer(transfer_ctx, amount)?;
+  token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
 
   emit!(CloseEvent {
+    version: CloseEvent::VERSION,
     authority: receipt.authority,
+    dao: proposal.dao,
+    proposal: proposal.key(),
     receipt: receipt.key(),
     mint: ctx.accounts.mint.key(),
-    amount: amount
+    action: receipt.action,
+    amount,
+    power: receipt.power,
   });
 
   Ok(())
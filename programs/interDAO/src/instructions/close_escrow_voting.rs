@@ -0,0 +1,75 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, proposal::*, receipt::*};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CloseEscrowVotingEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub proposal: Pubkey,
+  pub receipt: Pubkey,
+  pub escrow: Pubkey,
+  pub mint: Pubkey,
+  pub action: ReceiptAction,
+  pub power: u128,
+}
+
+impl CloseEscrowVotingEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct CloseEscrowVoting<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    seeds = [
+      b"proposal".as_ref(),
+      &proposal.index.to_le_bytes(),
+      &dao.key().to_bytes()
+    ],
+    bump,
+    has_one = dao
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+  #[account(
+    mut,
+    close = authority,
+    seeds = [
+      b"escrow_receipt".as_ref(),
+      &proposal.key().to_bytes(),
+      &receipt.escrow.to_bytes()
+    ],
+    bump,
+    has_one = authority,
+    has_one = proposal,
+  )]
+  pub receipt: Account<'info, Receipt>,
+}
+
+pub(crate) fn exec(ctx: Context<CloseEscrowVoting>) -> Result<()> {
+  let receipt = &ctx.accounts.receipt;
+  let proposal = &mut ctx.accounts.proposal;
+  // Validate permission & consensus
+  if let ProposalState::Draft | ProposalState::Voting = proposal.transition() {
+    return err!(ErrorCode::NotEndedProposal);
+  }
+
+  // The escrowed tokens never left the escrow, so only the receipt rent is reclaimed
+  emit!(CloseEscrowVotingEvent {
+    version: CloseEscrowVotingEvent::VERSION,
+    authority: receipt.authority,
+    dao: proposal.dao,
+    proposal: proposal.key(),
+    receipt: receipt.key(),
+    escrow: receipt.escrow,
+    mint: receipt.mint,
+    action: receipt.action,
+    power: receipt.power,
+  });
+
+  Ok(())
+}
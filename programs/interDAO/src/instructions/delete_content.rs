@@ -0,0 +1,40 @@
+use crate::schema::content::*;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DeleteContentEvent {
+  pub version: u8,
+  pub content: Pubkey,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub proposal: Pubkey,
+  pub parent: Pubkey,
+}
+
+impl DeleteContentEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct DeleteContent<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  // Replies keep pointing to a deleted parent
+  #[account(mut, close = authority, has_one = authority)]
+  pub content: Account<'info, Content>,
+}
+
+pub(crate) fn exec(ctx: Context<DeleteContent>) -> Result<()> {
+  let content = &ctx.accounts.content;
+
+  emit!(DeleteContentEvent {
+    version: DeleteContentEvent::VERSION,
+    content: content.key(),
+    authority: content.authority,
+    dao: content.dao,
+    proposal: content.proposal,
+    parent: content.parent,
+  });
+
+  Ok(())
+}
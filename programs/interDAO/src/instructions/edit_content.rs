@@ -0,0 +1,73 @@
+use crate::errors::ErrorCode;
+use crate::schema::{content::*, metadata::*};
+use crate::utils::{current_timestamp, is_valid_metadata};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ContentEvent {
+  pub version: u8,
+  pub content: Pubkey,
+  pub authority: Pubkey,
+  pub revision: u64,
+  pub old_metadata: [u8; 32],
+  pub new_metadata: [u8; 32],
+  pub old_hash_algorithm: HashAlgorithm,
+  pub new_hash_algorithm: HashAlgorithm,
+  pub old_metadata_uri: String,
+  pub new_metadata_uri: String,
+}
+
+impl ContentEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+#[instruction(metadata: [u8; 32], hash_algorithm: HashAlgorithm, metadata_uri: String)]
+pub struct EditContent<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    has_one = authority,
+    realloc = Content::LEN + metadata_uri.len(),
+    realloc::payer = authority,
+    realloc::zero = false
+  )]
+  pub content: Account<'info, Content>,
+  pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn exec(
+  ctx: Context<EditContent>,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+) -> Result<()> {
+  let content = &mut ctx.accounts.content;
+  // Validate data
+  if !is_valid_metadata(&metadata, hash_algorithm, &metadata_uri) {
+    return err!(ErrorCode::InvalidMetadataUri);
+  }
+  let old_metadata = content.metadata;
+  let old_hash_algorithm = content.hash_algorithm;
+  let old_metadata_uri = std::mem::replace(&mut content.metadata_uri, metadata_uri);
+  content.metadata = metadata;
+  content.hash_algorithm = hash_algorithm;
+  content.updated_at = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
+  content.revision = content.revision.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+  emit!(ContentEvent {
+    version: ContentEvent::VERSION,
+    content: content.key(),
+    authority: content.authority,
+    revision: content.revision,
+    old_metadata,
+    new_metadata: content.metadata,
+    old_hash_algorithm,
+    new_hash_algorithm: content.hash_algorithm,
+    old_metadata_uri,
+    new_metadata_uri: content.metadata_uri.clone(),
+  });
+
+  Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::errors::ErrorCode;
 use crate::schema::{dao::*, proposal::*, proposal_instruction::*};
-use crate::traits::{Age, Consensus, Permission};
+use crate::traits::Permission;
+use crate::utils::current_timestamp;
 use anchor_lang::{
   prelude::*,
   solana_program::{instruction::*, program::*},
@@ -8,8 +9,17 @@ use anchor_lang::{
 
 #[event]
 pub struct ExecuteProposalInstructionEvent {
+  pub version: u8,
   pub proposal: Pubkey,
+  pub proposal_instruction: Pubkey,
+  pub dao: Pubkey,
   pub caller: Pubkey,
+  pub index: u8,
+  pub state: ProposalState,
+}
+
+impl ExecuteProposalInstructionEvent {
+  pub const VERSION: u8 = 1;
 }
 
 #[derive(Accounts)]
@@ -47,7 +57,7 @@ pub struct ExecuteProposalInstruction<'info> {
   pub invoked_program: AccountInfo<'info>,
 }
 
-pub fn exec(ctx: Context<ExecuteProposalInstruction>) -> Result<()> {
+pub(crate) fn exec(ctx: Context<ExecuteProposalInstruction>) -> Result<()> {
   let dao = &ctx.accounts.dao;
   let proposal = &mut ctx.accounts.proposal;
   let proposal_instruction = &mut ctx.accounts.proposal_instruction;
@@ -55,17 +65,28 @@ pub fn exec(ctx: Context<ExecuteProposalInstruction>) -> Result<()> {
   if !dao.is_authorized_to_execute(ctx.accounts.caller.key()) {
     return err!(ErrorCode::NoPermission);
   }
-  if proposal.is_executed() {
-    return err!(ErrorCode::ExecutedProposal);
-  }
-  if !proposal.is_consented() || !proposal.is_ended() {
-    return err!(ErrorCode::NotConsentedProposal);
+  match proposal.transition() {
+    ProposalState::Succeeded | ProposalState::Executing => {}
+    ProposalState::Draft | ProposalState::Voting | ProposalState::Defeated => {
+      return err!(ErrorCode::NotConsentedProposal)
+    }
+    ProposalState::Queued => return err!(ErrorCode::TimelockedProposal),
+    ProposalState::Executed => return err!(ErrorCode::ExecutedProposal),
+    ProposalState::Cancelled => return err!(ErrorCode::VetoedProposal),
+    ProposalState::Expired => return err!(ErrorCode::ExpiredProposal),
   }
   // Validate proposal_instruction data
   if proposal_instruction.is_executed() {
     return err!(ErrorCode::ExecutedProposal);
   }
-  if proposal_instruction.index != proposal.total_executed {
+  if proposal_instruction.is_failed() {
+    return err!(ErrorCode::FailedProposalInstruction);
+  }
+  if proposal_instruction.index
+    != proposal
+      .next_instruction_index()
+      .ok_or(ErrorCode::Overflow)?
+  {
     return err!(ErrorCode::InvalidProposalIdx);
   }
   if proposal_instruction.accounts_len as usize != ctx.remaining_accounts.len() {
@@ -108,15 +129,22 @@ pub fn exec(ctx: Context<ExecuteProposalInstruction>) -> Result<()> {
   invoke_signed(&ix, ctx.remaining_accounts, seeds)?;
   // Success
   proposal_instruction.executed = true;
-  proposal.total_executed = proposal.total_executed + 1;
+  proposal.total_executed += 1;
+  proposal.last_processed_date = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
   // Check executed all instruction
-  if proposal.total_executed == proposal.total_instruction {
+  if proposal.next_instruction_index() == Some(proposal.total_instruction) {
     proposal.executed = true
   }
+  proposal.transition();
 
   emit!(ExecuteProposalInstructionEvent {
+    version: ExecuteProposalInstructionEvent::VERSION,
     proposal: proposal.key(),
-    caller: ctx.accounts.caller.key()
+    proposal_instruction: proposal_instruction.key(),
+    dao: proposal.dao,
+    caller: ctx.accounts.caller.key(),
+    index: proposal_instruction.index,
+    state: proposal.state,
   });
 
   Ok(())
@@ -0,0 +1,107 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, proposal::*, proposal_instruction::*};
+use crate::traits::{Age, Permission};
+use crate::utils::current_timestamp;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FailProposalInstructionEvent {
+  pub version: u8,
+  pub proposal: Pubkey,
+  pub proposal_instruction: Pubkey,
+  pub dao: Pubkey,
+  pub caller: Pubkey,
+  pub index: u8,
+  pub skipped: bool,
+}
+
+impl FailProposalInstructionEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct FailProposalInstruction<'info> {
+  pub caller: Signer<'info>,
+  #[account(
+    mut,
+    seeds = [
+      b"proposal".as_ref(),
+      &proposal.index.to_le_bytes(),
+      &dao.key().to_bytes()
+    ],
+    bump,
+    has_one = dao
+  )]
+  pub proposal: Account<'info, Proposal>,
+  #[account(
+    mut,
+    has_one = proposal
+  )]
+  pub proposal_instruction: Account<'info, ProposalInstruction>,
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<FailProposalInstruction>) -> Result<()> {
+  let dao = &ctx.accounts.dao;
+  let proposal = &mut ctx.accounts.proposal;
+  let proposal_instruction = &mut ctx.accounts.proposal_instruction;
+  // Validate permission & consensus
+  if !dao.is_authorized_to_execute(ctx.accounts.caller.key()) {
+    return err!(ErrorCode::NoPermission);
+  }
+  match proposal.transition() {
+    ProposalState::Succeeded | ProposalState::Executing => {}
+    ProposalState::Draft | ProposalState::Voting | ProposalState::Defeated => {
+      return err!(ErrorCode::NotConsentedProposal)
+    }
+    ProposalState::Queued => return err!(ErrorCode::TimelockedProposal),
+    ProposalState::Executed => return err!(ErrorCode::ExecutedProposal),
+    ProposalState::Cancelled => return err!(ErrorCode::VetoedProposal),
+    ProposalState::Expired => return err!(ErrorCode::ExpiredProposal),
+  }
+  // Validate proposal_instruction data
+  if proposal_instruction.is_executed() {
+    return err!(ErrorCode::ExecutedProposal);
+  }
+  if proposal_instruction.is_failed() {
+    return err!(ErrorCode::FailedProposalInstruction);
+  }
+  if proposal_instruction.index
+    != proposal
+      .next_instruction_index()
+      .ok_or(ErrorCode::Overflow)?
+  {
+    return err!(ErrorCode::InvalidProposalIdx);
+  }
+  // Executors can retry until the retry window passes
+  if !proposal.is_retry_window_passed() {
+    return err!(ErrorCode::RetryableProposalInstruction);
+  }
+
+  proposal_instruction.failed = true;
+  proposal.total_failed = proposal
+    .total_failed
+    .checked_add(1)
+    .ok_or(ErrorCode::Overflow)?;
+  // The next instruction gets its own retry window
+  proposal.last_processed_date = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
+  // Independent instructions are skipped, otherwise the remaining ones can't run anymore
+  if !proposal.independent_instructions
+    || proposal.next_instruction_index() == Some(proposal.total_instruction)
+  {
+    proposal.executed = true
+  }
+  proposal.transition();
+
+  emit!(FailProposalInstructionEvent {
+    version: FailProposalInstructionEvent::VERSION,
+    proposal: proposal.key(),
+    proposal_instruction: proposal_instruction.key(),
+    dao: proposal.dao,
+    caller: ctx.accounts.caller.key(),
+    index: proposal_instruction.index,
+    skipped: proposal.independent_instructions,
+  });
+
+  Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, proposal::*};
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FinalizeProposalEvent {
+  pub version: u8,
+  pub proposal: Pubkey,
+  pub dao: Pubkey,
+  pub caller: Pubkey,
+  pub state: ProposalState,
+}
+
+impl FinalizeProposalEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+  pub caller: Signer<'info>,
+  #[account(
+    mut,
+    seeds = [
+      b"proposal".as_ref(),
+      &proposal.index.to_le_bytes(),
+      &dao.key().to_bytes()
+    ],
+    bump,
+    has_one = dao
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<FinalizeProposal>) -> Result<()> {
+  let proposal = &mut ctx.accounts.proposal;
+  // Record the outcome once the campaign is over
+  let state = proposal.transition();
+  if let ProposalState::Draft | ProposalState::Voting = state {
+    return err!(ErrorCode::NotEndedProposal);
+  }
+
+  emit!(FinalizeProposalEvent {
+    version: FinalizeProposalEvent::VERSION,
+    proposal: proposal.key(),
+    dao: proposal.dao,
+    caller: ctx.accounts.caller.key(),
+    state,
+  });
+
+  Ok(())
+}
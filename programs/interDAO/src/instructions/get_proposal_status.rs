@@ -0,0 +1,29 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, proposal::*};
+use crate::utils::current_timestamp;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GetProposalStatus<'info> {
+  #[account(
+    seeds = [
+      b"proposal".as_ref(),
+      &proposal.index.to_le_bytes(),
+      &dao.key().to_bytes()
+    ],
+    bump,
+    has_one = dao
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+}
+
+///
+/// Read-only view returning the proposal status through the return data,
+/// so other programs can gate their actions on a proposal outcome by CPI
+///
+pub(crate) fn exec(ctx: Context<GetProposalStatus>) -> Result<ProposalStatus> {
+  let now = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
+  let proposal = &ctx.accounts.proposal;
+  Ok(proposal.status_at(proposal.key(), now))
+}
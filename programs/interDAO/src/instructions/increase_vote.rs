@@ -0,0 +1,159 @@
+use crate::errors::ErrorCode;
+use crate::interop::calculate_transfer_fee;
+use crate::schema::{dao::*, proposal::*, receipt::*};
+use crate::traits::Consensus;
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{associated_token, token_interface};
+
+#[event]
+pub struct IncreaseVoteEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub proposal: Pubkey,
+  pub receipt: Pubkey,
+  pub mint: Pubkey,
+  pub action: ReceiptAction,
+  pub amount: u64,
+  pub power: u128,
+}
+
+impl IncreaseVoteEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct IncreaseVote<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(mut, has_one = mint)]
+  pub src: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+  #[account(seeds = [b"treasurer".as_ref(), &proposal.key().to_bytes()], bump)]
+  /// CHECK: Just a pure account
+  pub treasurer: AccountInfo<'info>,
+  pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+  #[account(
+    mut,
+    token::mint = mint,
+    token::authority = treasurer,
+    address = associated_token::get_associated_token_address_with_program_id(
+      &treasurer.key(),
+      &mint.key(),
+      &token_program.key()
+    ) @ ErrorCode::InvalidAssociatedTokenAccount
+  )]
+  pub treasury: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+  #[account(
+    mut,
+    seeds = [
+      b"proposal".as_ref(),
+      &proposal.index.to_le_bytes(),
+      &dao.key().to_bytes()
+    ],
+    bump,
+    has_one = dao
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+  #[account(
+    mut,
+    seeds = [
+      b"receipt".as_ref(),
+      &receipt.index.to_le_bytes(),
+      &proposal.key().to_bytes(),
+      &authority.key().to_bytes()
+    ],
+    bump,
+    has_one = authority,
+    has_one = proposal,
+    has_one = mint,
+  )]
+  pub receipt: Account<'info, Receipt>,
+  #[account(mut)]
+  /// CHECK: Just a pure account
+  pub taxman: AccountInfo<'info>,
+  #[account(mut)]
+  /// CHECK: Just a pure account
+  pub revenueman: AccountInfo<'info>,
+  pub token_program: Interface<'info, token_interface::TokenInterface>,
+  pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+  pub system_program: Program<'info, System>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn exec(ctx: Context<IncreaseVote>, amount: u64, tax: u64, revenue: u64) -> Result<()> {
+  let receipt = &mut ctx.accounts.receipt;
+  let proposal = &mut ctx.accounts.proposal;
+  let mint = ctx.accounts.mint.key();
+  // Validate the voting house
+  if mint != ctx.accounts.dao.mint && proposal.house_of(mint) != House::Council {
+    return err!(ErrorCode::InvalidGovernanceMint);
+  }
+  // Validate permission & consensus
+  match proposal.transition() {
+    ProposalState::Voting => {}
+    ProposalState::Draft => return err!(ErrorCode::NotStartedProposal),
+    ProposalState::Executing | ProposalState::Executed => return err!(ErrorCode::ExecutedProposal),
+    _ => return err!(ErrorCode::EndedProposal),
+  }
+  // Deposited tokens may have been acquired after the snapshot
+  if proposal.snapshot_voting {
+    return err!(ErrorCode::SnapshotVoting);
+  }
+
+  // Charge protocol tax
+  if tax > 0 {
+    let tax_ctx = CpiContext::new(
+      ctx.accounts.system_program.to_account_info(),
+      system_program::Transfer {
+        from: ctx.accounts.authority.to_account_info(),
+        to: ctx.accounts.taxman.to_account_info(),
+      },
+    );
+    system_program::transfer(tax_ctx, tax)?;
+  }
+  // Charge DAO revenue
+  if revenue > 0 {
+    let revenue_ctx = CpiContext::new(
+      ctx.accounts.system_program.to_account_info(),
+      system_program::Transfer {
+        from: ctx.accounts.authority.to_account_info(),
+        to: ctx.accounts.revenueman.to_account_info(),
+      },
+    );
+    system_program::transfer(revenue_ctx, revenue)?;
+  }
+
+  // Lock tokens into the treasury (excluding the transfer fee withheld by Token-2022 mints)
+  let fee = calculate_transfer_fee(&ctx.accounts.mint.to_account_info(), amount)
+    .ok_or(ErrorCode::InvalidTransferFee)?;
+  let locked_amount = amount.checked_sub(fee).ok_or(ErrorCode::Overflow)?;
+  let transfer_ctx = CpiContext::new(
+    ctx.accounts.token_program.to_account_info(),
+    token_interface::TransferChecked {
+      from: ctx.accounts.src.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
+      to: ctx.accounts.treasury.to_account_info(),
+      authority: ctx.accounts.authority.to_account_info(),
+    },
+  );
+  token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+  // Count the votes
+  let (power, _) = proposal
+    .increase_vote(locked_amount, receipt)
+    .ok_or(ErrorCode::Overflow)?;
+
+  emit!(IncreaseVoteEvent {
+    version: IncreaseVoteEvent::VERSION,
+    authority: receipt.authority,
+    dao: ctx.accounts.dao.key(),
+    proposal: receipt.proposal,
+    receipt: receipt.key(),
+    mint: receipt.mint,
+    action: receipt.action,
+    amount: locked_amount,
+    power
+  });
+
+  Ok(())
+}
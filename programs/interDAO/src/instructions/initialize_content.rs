@@ -1,32 +1,142 @@
-use crate::schema::content::*;
+use crate::errors::ErrorCode;
+use crate::schema::{content::*, dao::*, metadata::*, proposal::*};
+use crate::utils::{current_timestamp, is_valid_metadata};
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+#[event]
+pub struct InitializeContentEvent {
+  pub version: u8,
+  pub content: Pubkey,
+  pub index: u64,
+  pub authority: Pubkey,
+  pub discriminator: [u8; 8],
+  pub metadata: [u8; 32],
+  pub hash_algorithm: HashAlgorithm,
+  pub metadata_uri: String,
+  pub dao: Pubkey,
+  pub proposal: Pubkey,
+  pub parent: Pubkey,
+}
+
+impl InitializeContentEvent {
+  pub const VERSION: u8 = 1;
+}
 
 #[derive(Accounts)]
-#[instruction(discriminator: [u8; 8])]
+#[instruction(
+  discriminator: [u8; 8],
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String
+)]
 pub struct InitializeContent<'info> {
   #[account(mut)]
   pub authority: Signer<'info>,
   #[account(
-    init_if_needed, payer = authority, space = Content::LEN,
+    init, payer = authority, space = Content::LEN + metadata_uri.len(),
     seeds = [
       b"content".as_ref(),
-      &discriminator,
-      &authority.key().to_bytes()
+      &dao.content_nonce.to_le_bytes(),
+      &dao.key().to_bytes()
     ],
     bump
   )]
   pub content: Account<'info, Content>,
+  #[account(mut)]
+  pub dao: Account<'info, Dao>,
+  // Omit the proposal to discuss the DAO itself
+  pub proposal: Option<Account<'info, Proposal>>,
+  // Omit the parent to start a new thread
+  pub parent: Option<Account<'info, Content>>,
+  // Required when the DAO gates content by governance token holdings.
+  // Holdings are only checked here: later edits stand even if the author sold the tokens
+  pub holdings: Option<Box<InterfaceAccount<'info, token_interface::TokenAccount>>>,
   pub system_program: Program<'info, System>,
 }
 
-pub fn exec(
+pub(crate) fn exec(
   ctx: Context<InitializeContent>,
   discriminator: [u8; 8],
   metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
 ) -> Result<()> {
+  let authority = ctx.accounts.authority.key();
+  let dao = &mut ctx.accounts.dao;
+  // Validate the discussion
+  let proposal = match &ctx.accounts.proposal {
+    Some(proposal) => {
+      if proposal.dao != dao.key() {
+        return err!(ErrorCode::InconsistentProposal);
+      }
+      proposal.key()
+    }
+    None => Pubkey::default(),
+  };
+  let parent = match &ctx.accounts.parent {
+    Some(parent) => {
+      if parent.key() == ctx.accounts.content.key()
+        || parent.dao != dao.key()
+        || parent.proposal != proposal
+      {
+        return err!(ErrorCode::InvalidContentParent);
+      }
+      parent.key()
+    }
+    None => Pubkey::default(),
+  };
+  if !is_valid_metadata(&metadata, hash_algorithm, &metadata_uri) {
+    return err!(ErrorCode::InvalidMetadataUri);
+  }
+  // Validate the holdings
+  if dao.content_threshold > 0 {
+    let holdings = ctx
+      .accounts
+      .holdings
+      .as_ref()
+      .ok_or(ErrorCode::InsufficientHoldings)?;
+    if holdings.owner != authority
+      || holdings.mint != dao.mint
+      || !dao.is_eligible_to_post(holdings.amount)
+    {
+      return err!(ErrorCode::InsufficientHoldings);
+    }
+  }
+
+  let now = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
   let content = &mut ctx.accounts.content;
-  content.authority = ctx.accounts.authority.key();
+  content.index = dao.content_nonce;
+  content.authority = authority;
   content.discriminator = discriminator;
   content.metadata = metadata;
+  content.dao = dao.key();
+  content.proposal = proposal;
+  content.parent = parent;
+  content.created_at = now;
+  content.updated_at = now;
+  content.revision = 0;
+  content.hash_algorithm = hash_algorithm;
+  content.metadata_uri = metadata_uri;
+  // Update dao data
+  dao.content_nonce = dao
+    .content_nonce
+    .checked_add(1)
+    .ok_or(ErrorCode::Overflow)?;
+
+  emit!(InitializeContentEvent {
+    version: InitializeContentEvent::VERSION,
+    content: content.key(),
+    index: content.index,
+    authority: content.authority,
+    discriminator,
+    metadata,
+    hash_algorithm: content.hash_algorithm,
+    metadata_uri: content.metadata_uri.clone(),
+    dao: content.dao,
+    proposal: content.proposal,
+    parent: content.parent,
+  });
+
   Ok(())
 }
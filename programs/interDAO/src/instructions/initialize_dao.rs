@@ -1,23 +1,45 @@
-use crate::schema::dao::*;
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, metadata::*};
+use crate::utils::is_valid_metadata;
 use anchor_lang::prelude::*;
-use anchor_spl::token;
+use anchor_spl::token_interface;
 
 #[event]
 pub struct InitializeDAOEvent {
+  pub version: u8,
   pub dao: Pubkey,
   pub authority: Pubkey,
   pub mint: Pubkey,
   pub regime: DaoRegime,
   pub supply: u64,
+  pub metadata: [u8; 32],
+  pub hash_algorithm: HashAlgorithm,
+  pub metadata_uri: String,
   pub is_nft: bool,
   pub is_public: bool,
+  pub min_voting_duration: i64,
+  pub max_voting_duration: i64,
+  pub min_notice_period: i64,
+  pub execution_window: i64,
+  pub retry_window: i64,
+}
+
+impl InitializeDAOEvent {
+  pub const VERSION: u8 = 1;
 }
 
 #[derive(Accounts)]
+#[instruction(
+  regime: DaoRegime,
+  supply: u64,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String
+)]
 pub struct InitializeDAO<'info> {
   #[account(mut)]
   pub authority: Signer<'info>,
-  #[account(init, payer = authority, space = Dao::LEN)]
+  #[account(init, payer = authority, space = Dao::LEN + metadata_uri.len())]
   pub dao: Account<'info, Dao>,
   #[account(
     seeds = [
@@ -28,20 +50,40 @@ pub struct InitializeDAO<'info> {
   )]
   /// CHECK: Just a pure account
   pub master: AccountInfo<'info>,
-  pub mint: Account<'info, token::Mint>,
+  pub mint: InterfaceAccount<'info, token_interface::Mint>,
   pub system_program: Program<'info, System>,
   pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn exec(
+pub(crate) fn exec(
   ctx: Context<InitializeDAO>,
   regime: DaoRegime,
   supply: u64,
   metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
   is_nft: bool,
   is_public: bool,
+  min_voting_duration: i64,
+  max_voting_duration: i64,
+  min_notice_period: i64,
+  execution_window: i64,
+  retry_window: i64,
 ) -> Result<()> {
   let dao = &mut ctx.accounts.dao;
+  // Validate data
+  if !Dao::is_valid_proposal_bounds(
+    min_voting_duration,
+    max_voting_duration,
+    min_notice_period,
+    execution_window,
+    retry_window,
+  ) {
+    return err!(ErrorCode::InvalidProposalBounds);
+  }
+  if !is_valid_metadata(&metadata, hash_algorithm, &metadata_uri) {
+    return err!(ErrorCode::InvalidMetadataUri);
+  }
   dao.authority = ctx.accounts.authority.key();
   dao.master = ctx.accounts.master.key();
   dao.mint = ctx.accounts.mint.key();
@@ -51,15 +93,42 @@ pub fn exec(
   dao.metadata = metadata;
   dao.is_nft = is_nft;
   dao.is_public = is_public;
+  dao.guardians = vec![];
+  dao.veto_threshold = 0;
+  dao.timelock = 0;
+  dao.council_mint = Pubkey::default();
+  dao.council_supply = 0;
+  dao.pending_authority = Pubkey::default();
+  dao.min_voting_duration = min_voting_duration;
+  dao.max_voting_duration = max_voting_duration;
+  dao.min_notice_period = min_notice_period;
+  dao.execution_window = execution_window;
+  dao.retry_window = retry_window;
+  dao.early_finalization = false;
+  dao.one_direction_voting = false;
+  dao.snapshot_voting = false;
+  dao.content_threshold = 0;
+  dao.content_nonce = 0;
+  dao.hash_algorithm = hash_algorithm;
+  dao.metadata_uri = metadata_uri;
 
   emit!(InitializeDAOEvent {
+    version: InitializeDAOEvent::VERSION,
     dao: dao.key(),
     authority: dao.authority,
     mint: dao.mint,
     regime: dao.regime,
     supply: dao.supply,
-    is_nft: is_nft,
-    is_public: is_public
+    metadata: dao.metadata,
+    hash_algorithm: dao.hash_algorithm,
+    metadata_uri: dao.metadata_uri.clone(),
+    is_nft,
+    is_public,
+    min_voting_duration,
+    max_voting_duration,
+    min_notice_period,
+    execution_window,
+    retry_window
   });
 
   Ok(())
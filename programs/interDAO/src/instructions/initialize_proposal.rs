@@ -1,28 +1,46 @@
 use crate::errors::ErrorCode;
-use crate::schema::{dao::*, proposal::*};
+use crate::schema::{dao::*, metadata::*, proposal::*};
 use crate::traits::Permission;
-use crate::utils::current_timestamp;
+use crate::utils::{current_timestamp, is_valid_metadata};
 use anchor_lang::{prelude::*, system_program};
 
-const ONE_DAY: i64 = 1; // 86400
-const ONE_QUATER: i64 = 7776000;
-
 #[event]
 pub struct InitializeProposalEvent {
+  pub version: u8,
   pub proposal: Pubkey,
   pub dao: Pubkey,
   pub caller: Pubkey,
+  pub index: u64,
+  pub consensus_mechanism: ConsensusMechanism,
   pub quorum: ConsensusQuorum,
+  pub start_date: i64,
+  pub end_date: i64,
+  pub metadata: [u8; 32],
+  pub hash_algorithm: HashAlgorithm,
+  pub metadata_uri: String,
+}
+
+impl InitializeProposalEvent {
+  pub const VERSION: u8 = 1;
 }
 
 #[derive(Accounts)]
+#[instruction(
+  consensus_mechanism: ConsensusMechanism,
+  consensus_quorum: ConsensusQuorum,
+  start_date: i64,
+  end_date: i64,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String
+)]
 pub struct InitializeProposal<'info> {
   #[account(mut)]
   pub caller: Signer<'info>,
   #[account(
     init,
     payer = caller,
-    space = Proposal::LEN,
+    space = Proposal::LEN + metadata_uri.len(),
     seeds = [
       b"proposal".as_ref(),
       &dao.nonce.to_le_bytes(),
@@ -44,13 +62,16 @@ pub struct InitializeProposal<'info> {
   pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn exec(
+pub(crate) fn exec(
   ctx: Context<InitializeProposal>,
   consensus_mechanism: ConsensusMechanism,
   consensus_quorum: ConsensusQuorum,
   start_date: i64,
   end_date: i64,
   metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+  independent_instructions: bool,
   tax: u64,
   revenue: u64,
 ) -> Result<()> {
@@ -61,21 +82,10 @@ pub fn exec(
     return err!(ErrorCode::NoPermission);
   }
   // Validate data
-  if start_date < current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)? {
-    return err!(ErrorCode::InvalidStartDate);
-  }
-  if end_date <= start_date
-    || end_date <= current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?
-    || end_date
-      .checked_sub(start_date)
-      .ok_or(ErrorCode::InvalidEndDate)?
-      < ONE_DAY
-    || end_date
-      .checked_sub(start_date)
-      .ok_or(ErrorCode::InvalidEndDate)?
-      > ONE_QUATER
-  {
-    return err!(ErrorCode::InvalidEndDate);
+  let now = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
+  dao.validate_proposal_dates(now, start_date, end_date)?;
+  if !is_valid_metadata(&metadata, hash_algorithm, &metadata_uri) {
+    return err!(ErrorCode::InvalidMetadataUri);
   }
 
   // Charge protocol tax
@@ -115,15 +125,44 @@ pub fn exec(
   proposal.voting_for_power = 0;
   proposal.voting_against_power = 0;
   proposal.supply = dao.supply;
+  proposal.timelock = dao.timelock;
+  proposal.guardians = dao.guardians.clone();
+  proposal.veto_threshold = dao.veto_threshold;
+  proposal.vetoes = 0;
+  proposal.vetoed = false;
+  proposal.council_mint = dao.council_mint;
+  proposal.council_supply = dao.council_supply;
+  proposal.council_for_power = 0;
+  proposal.council_against_power = 0;
+  proposal.early_finalization = dao.early_finalization;
+  proposal.decided_date = 0;
+  proposal.one_direction_voting = dao.one_direction_voting;
+  proposal.snapshot_voting = dao.snapshot_voting;
+  proposal.execution_window = dao.execution_window;
+  proposal.state = ProposalState::Draft;
+  proposal.independent_instructions = independent_instructions;
+  proposal.total_failed = 0;
+  proposal.retry_window = dao.retry_window;
+  proposal.last_processed_date = 0;
+  proposal.hash_algorithm = hash_algorithm;
+  proposal.metadata_uri = metadata_uri;
 
   // Update dao data
   dao.nonce = dao.nonce.checked_add(1).ok_or(ErrorCode::Overflow)?;
 
   emit!(InitializeProposalEvent {
+    version: InitializeProposalEvent::VERSION,
     proposal: proposal.key(),
     dao: proposal.dao,
     caller: proposal.creator,
+    index: proposal.index,
+    consensus_mechanism: proposal.consensus_mechanism,
     quorum: proposal.consensus_quorum,
+    start_date: proposal.start_date,
+    end_date: proposal.end_date,
+    metadata: proposal.metadata,
+    hash_algorithm: proposal.hash_algorithm,
+    metadata_uri: proposal.metadata_uri.clone(),
   });
 
   Ok(())
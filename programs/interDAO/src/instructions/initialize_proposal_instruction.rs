@@ -1,4 +1,3 @@
-use crate::constants::*;
 use crate::errors::ErrorCode;
 use crate::schema::{dao::*, proposal::*, proposal_instruction::*};
 use crate::traits::Age;
@@ -8,6 +7,7 @@ use num_traits::ToPrimitive;
 
 #[event]
 pub struct InitializeProposalInstructionEvent {
+  pub version: u8,
   pub proposal: Pubkey,
   pub proposal_instruction: Pubkey,
   pub dao: Pubkey,
@@ -17,6 +17,10 @@ pub struct InitializeProposalInstructionEvent {
   pub accounts: Vec<InvokedAccount>,
 }
 
+impl InitializeProposalInstructionEvent {
+  pub const VERSION: u8 = 1;
+}
+
 #[derive(Accounts)]
 #[instruction(data: Vec<u8>, pubkeys: Vec<Pubkey>)]
 pub struct InitializeProposalInstruction<'info> {
@@ -26,13 +30,17 @@ pub struct InitializeProposalInstruction<'info> {
   #[account(
     init,
     payer = caller,
-    space = ProposalInstruction::HEADER_LEN + VECTOR_OVERHEAD_SIZE + data.len() + VECTOR_OVERHEAD_SIZE + pubkeys.len() * INVOKED_ACCOUNT_SIZE,
+    space = ProposalInstruction::space(data.len(), pubkeys.len()),
   )]
   pub proposal_instruction: Account<'info, ProposalInstruction>,
   /// CHECK: Just a pure account
   pub invoked_program: AccountInfo<'info>,
 
-  #[account(mut)]
+  #[account(
+    mut,
+    has_one = dao,
+    constraint = proposal.creator == caller.key() @ ErrorCode::NoPermission
+  )]
   pub proposal: Account<'info, Proposal>,
   #[account(mut)]
   pub dao: Account<'info, Dao>,
@@ -41,7 +49,7 @@ pub struct InitializeProposalInstruction<'info> {
   pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn exec(
+pub(crate) fn exec(
   ctx: Context<InitializeProposalInstruction>,
   tx_idx: u8,
   data: Vec<u8>,
@@ -83,6 +91,7 @@ pub fn exec(
   proposal_instruction.index = proposal.total_instruction;
   proposal_instruction.tx_index = tx_idx;
   proposal_instruction.executed = false;
+  proposal_instruction.failed = false;
   // Data for the inter action
   proposal_instruction.data_len = data.len().to_u64().ok_or(ErrorCode::Overflow)?;
   proposal_instruction.data = data.clone();
@@ -91,11 +100,16 @@ pub fn exec(
   proposal_instruction.accounts = accounts.clone();
   // Program to execute
   proposal_instruction.invoked_program = ctx.accounts.invoked_program.key();
+  proposal_instruction.kind = ProposalInstructionKind::Raw;
 
   // Update proposal data
-  proposal.total_instruction = proposal.total_instruction + 1;
+  proposal.total_instruction = proposal
+    .total_instruction
+    .checked_add(1)
+    .ok_or(ErrorCode::Overflow)?;
 
   emit!(InitializeProposalInstructionEvent {
+    version: InitializeProposalInstructionEvent::VERSION,
     proposal: proposal.key(),
     proposal_instruction: proposal_instruction.key(),
     dao: proposal.dao,
@@ -0,0 +1,181 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, proposal::*, proposal_instruction::*};
+use crate::traits::Age;
+use crate::traits::Permission;
+use anchor_lang::{
+  prelude::*,
+  solana_program::{program_option::COption, system_instruction},
+};
+use anchor_spl::{associated_token, token_interface};
+use num_traits::ToPrimitive;
+
+#[event]
+pub struct InitializeProposalTemplateEvent {
+  pub version: u8,
+  pub proposal: Pubkey,
+  pub proposal_instruction: Pubkey,
+  pub dao: Pubkey,
+  pub caller: Pubkey,
+  pub kind: ProposalInstructionKind,
+  pub invoked_program: Pubkey,
+  pub data: Vec<u8>,
+  pub accounts: Vec<InvokedAccount>,
+}
+
+impl InitializeProposalTemplateEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+#[instruction(tx_idx: u8, kind: ProposalInstructionKind)]
+pub struct InitializeProposalTemplate<'info> {
+  #[account(mut)]
+  pub caller: Signer<'info>,
+  #[account(
+    init,
+    payer = caller,
+    space = ProposalInstruction::space(kind.data_len(), kind.accounts_len()),
+  )]
+  pub proposal_instruction: Account<'info, ProposalInstruction>,
+  #[account(
+    mut,
+    has_one = dao,
+    constraint = proposal.creator == caller.key() @ ErrorCode::NoPermission
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+  #[account(seeds = [b"master".as_ref(), &dao.key().to_bytes()], bump)]
+  /// CHECK: Just a pure account
+  pub master: AccountInfo<'info>,
+  // Required by token templates
+  pub mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
+  pub token_program: Option<Interface<'info, token_interface::TokenInterface>>,
+  pub system_program: Program<'info, System>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn exec(
+  ctx: Context<InitializeProposalTemplate>,
+  tx_idx: u8,
+  kind: ProposalInstructionKind,
+) -> Result<()> {
+  let dao = &ctx.accounts.dao;
+  let proposal = &mut ctx.accounts.proposal;
+  let proposal_instruction = &mut ctx.accounts.proposal_instruction;
+  let master = ctx.accounts.master.key();
+  // Validate permission
+  if !dao.is_authorized_to_propose(ctx.accounts.caller.key()) {
+    return err!(ErrorCode::NoPermission);
+  }
+  // Validate proposal state
+  if proposal.is_started() {
+    return err!(ErrorCode::StartedProposal);
+  }
+
+  // Token templates require the mint owned by the token program
+  let (mint_account, token_program) = (&ctx.accounts.mint, &ctx.accounts.token_program);
+  let token_accounts = |mint: Pubkey, amount: u64| -> Result<_> {
+    match (mint_account.as_ref(), token_program.as_ref()) {
+      (Some(mint_account), Some(token_program))
+        if amount > 0
+          && mint_account.key() == mint
+          && *mint_account.to_account_info().owner == token_program.key() =>
+      {
+        Ok((mint_account, token_program))
+      }
+      _ => err!(ErrorCode::InvalidProposalTemplate),
+    }
+  };
+
+  // Build the instruction from the template
+  let ix = match kind {
+    ProposalInstructionKind::Raw => return err!(ErrorCode::InvalidProposalTemplate),
+    ProposalInstructionKind::TokenTransfer { mint, dst, amount } => {
+      let (mint_account, token_program) = token_accounts(mint, amount)?;
+      let src = associated_token::get_associated_token_address_with_program_id(
+        &master,
+        &mint,
+        &token_program.key(),
+      );
+      spl_token_2022::instruction::transfer_checked(
+        &token_program.key(),
+        &src,
+        &mint,
+        &dst,
+        &master,
+        &[],
+        amount,
+        mint_account.decimals,
+      )?
+    }
+    ProposalInstructionKind::SolTransfer { dst, lamports } => {
+      if lamports == 0 {
+        return err!(ErrorCode::InvalidProposalTemplate);
+      }
+      system_instruction::transfer(&master, &dst, lamports)
+    }
+    ProposalInstructionKind::MintTo { mint, dst, amount } => {
+      let (mint_account, token_program) = token_accounts(mint, amount)?;
+      if mint_account.mint_authority != COption::Some(master) {
+        return err!(ErrorCode::InvalidMintAuthority);
+      }
+      spl_token_2022::instruction::mint_to_checked(
+        &token_program.key(),
+        &mint,
+        &dst,
+        &master,
+        &[],
+        amount,
+        mint_account.decimals,
+      )?
+    }
+  };
+  if ix.data.len() != kind.data_len() || ix.accounts.len() != kind.accounts_len() {
+    return err!(ErrorCode::InvalidDataLength);
+  }
+  let accounts: Vec<InvokedAccount> = ix
+    .accounts
+    .iter()
+    .map(|meta| InvokedAccount {
+      pubkey: meta.pubkey,
+      is_signer: meta.is_signer,
+      is_writable: meta.is_writable,
+      is_master: meta.pubkey == master,
+    })
+    .collect();
+
+  proposal_instruction.proposal = proposal.key();
+  proposal_instruction.index = proposal.total_instruction;
+  proposal_instruction.tx_index = tx_idx;
+  proposal_instruction.executed = false;
+  proposal_instruction.failed = false;
+  // Data for the inter action
+  proposal_instruction.data_len = ix.data.len().to_u64().ok_or(ErrorCode::Overflow)?;
+  proposal_instruction.data = ix.data;
+  // Accounts for the inter action
+  proposal_instruction.accounts_len = accounts.len().to_u8().ok_or(ErrorCode::Overflow)?;
+  proposal_instruction.accounts = accounts;
+  // Program to execute
+  proposal_instruction.invoked_program = ix.program_id;
+  proposal_instruction.kind = kind;
+
+  // Update proposal data
+  proposal.total_instruction = proposal
+    .total_instruction
+    .checked_add(1)
+    .ok_or(ErrorCode::Overflow)?;
+
+  emit!(InitializeProposalTemplateEvent {
+    version: InitializeProposalTemplateEvent::VERSION,
+    proposal: proposal.key(),
+    proposal_instruction: proposal_instruction.key(),
+    dao: proposal.dao,
+    caller: ctx.accounts.caller.key(),
+    kind,
+    invoked_program: proposal_instruction.invoked_program,
+    data: proposal_instruction.data.clone(),
+    accounts: proposal_instruction.accounts.clone(),
+  });
+
+  Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::errors::ErrorCode;
+use crate::interop::calculate_transfer_fee;
+use crate::schema::{dao::*, escrow::*};
+use crate::utils::current_timestamp;
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token_interface};
+
+#[event]
+pub struct LockEscrowEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub escrow: Pubkey,
+  pub mint: Pubkey,
+  pub amount: u64,
+  pub unlocked_date: i64,
+}
+
+impl LockEscrowEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct LockEscrow<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(mut, has_one = mint)]
+  pub src: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+  #[account(seeds = [b"escrow_treasurer".as_ref(), &dao.key().to_bytes()], bump)]
+  /// CHECK: Just a pure account
+  pub escrow_treasurer: AccountInfo<'info>,
+  #[account(constraint = mint.key() == dao.mint || mint.key() == dao.council_mint @ ErrorCode::InvalidGovernanceMint)]
+  pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+  #[account(
+    mut,
+    address = associated_token::get_associated_token_address_with_program_id(
+      &escrow_treasurer.key(),
+      &mint.key(),
+      &token_program.key()
+    ) @ ErrorCode::InvalidAssociatedTokenAccount
+  )]
+  /// CHECK: Created on demand as the associated token account of the escrow treasurer
+  pub escrow_treasury: AccountInfo<'info>,
+  pub dao: Account<'info, Dao>,
+  #[account(
+    init_if_needed,
+    payer = authority,
+    space = Escrow::LEN,
+    seeds = [
+      b"escrow".as_ref(),
+      &dao.key().to_bytes(),
+      &mint.key().to_bytes(),
+      &authority.key().to_bytes()
+    ],
+    bump
+  )]
+  pub escrow: Account<'info, Escrow>,
+  pub token_program: Interface<'info, token_interface::TokenInterface>,
+  pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+  pub system_program: Program<'info, System>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn exec(ctx: Context<LockEscrow>, amount: u64, unlocked_date: i64) -> Result<()> {
+  let escrow = &mut ctx.accounts.escrow;
+  let now = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
+  // Validate data. Locks can only be extended
+  if unlocked_date <= now
+    || unlocked_date < escrow.unlocked_date
+    || unlocked_date.checked_sub(now).ok_or(ErrorCode::Overflow)? > Escrow::MAX_LOCK_DURATION
+  {
+    return err!(ErrorCode::InvalidLockDuration);
+  }
+
+  // Init escrow data
+  if escrow.authority == Pubkey::default() {
+    escrow.authority = ctx.accounts.authority.key();
+    escrow.dao = ctx.accounts.dao.key();
+    escrow.mint = ctx.accounts.mint.key();
+    escrow.locked_date = now;
+  }
+  escrow.unlocked_date = unlocked_date;
+  // Create the escrow treasury on demand
+  let create_ctx = CpiContext::new(
+    ctx.accounts.associated_token_program.to_account_info(),
+    associated_token::Create {
+      payer: ctx.accounts.authority.to_account_info(),
+      associated_token: ctx.accounts.escrow_treasury.to_account_info(),
+      authority: ctx.accounts.escrow_treasurer.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
+      system_program: ctx.accounts.system_program.to_account_info(),
+      token_program: ctx.accounts.token_program.to_account_info(),
+    },
+  );
+  associated_token::create_idempotent(create_ctx)?;
+  // Lock tokens into the escrow treasury (excluding the transfer fee withheld by Token-2022 mints)
+  let mut locked_amount = 0;
+  if amount > 0 {
+    let fee = calculate_transfer_fee(&ctx.accounts.mint.to_account_info(), amount)
+      .ok_or(ErrorCode::InvalidTransferFee)?;
+    locked_amount = amount.checked_sub(fee).ok_or(ErrorCode::Overflow)?;
+    let transfer_ctx = CpiContext::new(
+      ctx.accounts.token_program.to_account_info(),
+      token_interface::TransferChecked {
+        from: ctx.accounts.src.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.escrow_treasury.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+      },
+    );
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+    escrow.amount = escrow
+      .amount
+      .checked_add(locked_amount)
+      .ok_or(ErrorCode::Overflow)?;
+  }
+
+  escrow.checkpoint(now);
+
+  emit!(LockEscrowEvent {
+    version: LockEscrowEvent::VERSION,
+    authority: escrow.authority,
+    dao: escrow.dao,
+    escrow: escrow.key(),
+    mint: escrow.mint,
+    amount: locked_amount,
+    unlocked_date
+  });
+
+  Ok(())
+}
@@ -4,8 +4,16 @@ pub mod initialize_proposal;
 pub use initialize_proposal::*;
 pub mod initialize_proposal_instruction;
 pub use initialize_proposal_instruction::*;
+pub mod initialize_proposal_template;
+pub use initialize_proposal_template::*;
 pub mod initialize_content;
 pub use initialize_content::*;
+pub mod amend_proposal;
+pub use amend_proposal::*;
+pub mod replace_proposal_instruction;
+pub use replace_proposal_instruction::*;
+pub mod remove_proposal_instruction;
+pub use remove_proposal_instruction::*;
 pub mod execute_proposal_instruction;
 pub use execute_proposal_instruction::*;
 pub mod vote_for;
@@ -16,15 +24,59 @@ pub mod vote_against;
 pub use vote_against::*;
 pub mod vote_nft_against;
 pub use vote_nft_against::*;
+pub mod increase_vote;
+pub use increase_vote::*;
+pub mod lock_escrow;
+pub use lock_escrow::*;
+pub mod withdraw_escrow;
+pub use withdraw_escrow::*;
+pub mod vote_escrow_for;
+pub use vote_escrow_for::*;
+pub mod vote_escrow_against;
+pub use vote_escrow_against::*;
+pub mod fail_proposal_instruction;
+pub use fail_proposal_instruction::*;
+pub mod finalize_proposal;
+pub use finalize_proposal::*;
 pub mod close;
 pub use close::*;
 pub mod close_nft_voting;
 pub use close_nft_voting::*;
+pub mod close_escrow_voting;
+pub use close_escrow_voting::*;
 pub mod transfer_authority;
 pub use transfer_authority::*;
+pub mod accept_authority;
+pub use accept_authority::*;
+pub mod cancel_authority_transfer;
+pub use cancel_authority_transfer::*;
+pub mod renounce_authority;
+pub use renounce_authority::*;
 pub mod update_dao_regime;
 pub use update_dao_regime::*;
 pub mod update_dao_metadata;
 pub use update_dao_metadata::*;
 pub mod update_supply;
 pub use update_supply::*;
+pub mod update_proposal_bounds;
+pub use update_proposal_bounds::*;
+pub mod update_early_finalization;
+pub use update_early_finalization::*;
+pub mod update_one_direction_voting;
+pub use update_one_direction_voting::*;
+pub mod update_snapshot_voting;
+pub use update_snapshot_voting::*;
+pub mod update_dao_guardians;
+pub use update_dao_guardians::*;
+pub mod update_dao_council;
+pub use update_dao_council::*;
+pub mod veto_proposal;
+pub use veto_proposal::*;
+pub mod get_proposal_status;
+pub use get_proposal_status::*;
+pub mod edit_content;
+pub use edit_content::*;
+pub mod delete_content;
+pub use delete_content::*;
+pub mod update_content_threshold;
+pub use update_content_threshold::*;
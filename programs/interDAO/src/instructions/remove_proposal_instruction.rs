@@ -0,0 +1,81 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, proposal::*, proposal_instruction::*};
+use crate::traits::Age;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RemoveProposalInstructionEvent {
+  pub version: u8,
+  pub proposal: Pubkey,
+  pub proposal_instruction: Pubkey,
+  pub dao: Pubkey,
+  pub caller: Pubkey,
+  pub index: u8,
+}
+
+impl RemoveProposalInstructionEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct RemoveProposalInstruction<'info> {
+  #[account(mut)]
+  pub caller: Signer<'info>,
+  #[account(
+    mut,
+    close = caller,
+    has_one = proposal
+  )]
+  pub proposal_instruction: Account<'info, ProposalInstruction>,
+  #[account(
+    mut,
+    has_one = dao,
+    constraint = proposal.creator == caller.key() @ ErrorCode::NoPermission
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<RemoveProposalInstruction>) -> Result<()> {
+  let proposal = &mut ctx.accounts.proposal;
+  let proposal_instruction = &ctx.accounts.proposal_instruction;
+  // Validate proposal state
+  if proposal.is_started() {
+    return err!(ErrorCode::StartedProposal);
+  }
+  // Keep the instruction indices contiguous by shifting the following
+  // instructions, passed in order as remaining accounts, down by one
+  let following = proposal
+    .total_instruction
+    .checked_sub(proposal_instruction.index)
+    .and_then(|count| count.checked_sub(1))
+    .ok_or(ErrorCode::InvalidProposalIdx)?;
+  if ctx.remaining_accounts.len() != following as usize {
+    return err!(ErrorCode::MissingFollowingProposalInstructions);
+  }
+  for (offset, info) in ctx.remaining_accounts.iter().enumerate() {
+    let mut next = Account::<ProposalInstruction>::try_from(info)?;
+    if next.proposal != proposal.key() {
+      return err!(ErrorCode::InconsistentProposal);
+    }
+    if next.index as usize != proposal_instruction.index as usize + offset + 1 {
+      return err!(ErrorCode::InvalidProposalIdx);
+    }
+    next.index -= 1;
+    next.exit(ctx.program_id)?;
+  }
+
+  // Update proposal data
+  proposal.total_instruction -= 1;
+
+  emit!(RemoveProposalInstructionEvent {
+    version: RemoveProposalInstructionEvent::VERSION,
+    proposal: proposal.key(),
+    proposal_instruction: proposal_instruction.key(),
+    dao: proposal.dao,
+    caller: ctx.accounts.caller.key(),
+    index: proposal_instruction.index,
+  });
+
+  Ok(())
+}
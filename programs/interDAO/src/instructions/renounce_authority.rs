@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RenounceAuthorityEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+}
+
+impl RenounceAuthorityEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct RenounceAuthority<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<RenounceAuthority>) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  // Only the community can run the DAO afterwards
+  if dao.regime != DaoRegime::Autonomous {
+    return err!(ErrorCode::NotAutonomousDao);
+  }
+  dao.authority = dao.master;
+  dao.pending_authority = Pubkey::default();
+
+  emit!(RenounceAuthorityEvent {
+    version: RenounceAuthorityEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    dao: dao.key()
+  });
+
+  Ok(())
+}
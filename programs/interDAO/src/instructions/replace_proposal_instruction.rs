@@ -0,0 +1,115 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, proposal::*, proposal_instruction::*};
+use crate::traits::Age;
+use anchor_lang::prelude::*;
+use num_traits::ToPrimitive;
+
+#[event]
+pub struct ReplaceProposalInstructionEvent {
+  pub version: u8,
+  pub proposal: Pubkey,
+  pub proposal_instruction: Pubkey,
+  pub dao: Pubkey,
+  pub caller: Pubkey,
+  pub index: u8,
+  pub old_invoked_program: Pubkey,
+  pub new_invoked_program: Pubkey,
+  pub old_data: Vec<u8>,
+  pub new_data: Vec<u8>,
+  pub old_accounts: Vec<InvokedAccount>,
+  pub new_accounts: Vec<InvokedAccount>,
+}
+
+impl ReplaceProposalInstructionEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+#[instruction(data: Vec<u8>, pubkeys: Vec<Pubkey>)]
+pub struct ReplaceProposalInstruction<'info> {
+  #[account(mut)]
+  pub caller: Signer<'info>,
+  #[account(
+    mut,
+    has_one = proposal,
+    realloc = ProposalInstruction::space(data.len(), pubkeys.len()),
+    realloc::payer = caller,
+    realloc::zero = false
+  )]
+  pub proposal_instruction: Account<'info, ProposalInstruction>,
+  /// CHECK: Just a pure account
+  pub invoked_program: AccountInfo<'info>,
+  #[account(
+    has_one = dao,
+    constraint = proposal.creator == caller.key() @ ErrorCode::NoPermission
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+  pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn exec(
+  ctx: Context<ReplaceProposalInstruction>,
+  tx_idx: u8,
+  data: Vec<u8>,
+  pubkeys: Vec<Pubkey>,
+  is_signers: Vec<bool>,
+  is_writables: Vec<bool>,
+  is_masters: Vec<bool>,
+) -> Result<()> {
+  let proposal = &ctx.accounts.proposal;
+  let proposal_instruction = &mut ctx.accounts.proposal_instruction;
+  // Validate data
+  if pubkeys.len() != is_signers.len()
+    || pubkeys.len() != is_writables.len()
+    || pubkeys.len() != is_masters.len()
+  {
+    return err!(ErrorCode::InvalidDataLength);
+  }
+  // Validate proposal state
+  if proposal.is_started() {
+    return err!(ErrorCode::StartedProposal);
+  }
+
+  let mut accounts = Vec::with_capacity(pubkeys.len());
+  for i in 0..pubkeys.len() {
+    accounts.push(InvokedAccount {
+      pubkey: pubkeys[i],
+      is_signer: is_signers[i],
+      is_writable: is_writables[i],
+      is_master: is_masters[i],
+    });
+  }
+
+  let old_invoked_program = proposal_instruction.invoked_program;
+  let old_data = proposal_instruction.data.clone();
+  let old_accounts = proposal_instruction.accounts.clone();
+
+  proposal_instruction.tx_index = tx_idx;
+  // Data for the inter action
+  proposal_instruction.data_len = data.len().to_u64().ok_or(ErrorCode::Overflow)?;
+  proposal_instruction.data = data;
+  // Accounts for the inter action
+  proposal_instruction.accounts_len = accounts.len().to_u8().ok_or(ErrorCode::Overflow)?;
+  proposal_instruction.accounts = accounts;
+  // Program to execute
+  proposal_instruction.invoked_program = ctx.accounts.invoked_program.key();
+  proposal_instruction.kind = ProposalInstructionKind::Raw;
+
+  emit!(ReplaceProposalInstructionEvent {
+    version: ReplaceProposalInstructionEvent::VERSION,
+    proposal: proposal.key(),
+    proposal_instruction: proposal_instruction.key(),
+    dao: proposal.dao,
+    caller: ctx.accounts.caller.key(),
+    index: proposal_instruction.index,
+    old_invoked_program,
+    new_invoked_program: proposal_instruction.invoked_program,
+    old_data,
+    new_data: proposal_instruction.data.clone(),
+    old_accounts,
+    new_accounts: proposal_instruction.accounts.clone(),
+  });
+
+  Ok(())
+}
@@ -1,30 +1,42 @@
+use crate::errors::ErrorCode;
 use crate::schema::dao::*;
+use crate::traits::Permission;
 use anchor_lang::prelude::*;
 
 #[event]
 pub struct TransferAuthorityEvent {
+  pub version: u8,
   pub authority: Pubkey,
   pub new_authority: Pubkey,
   pub dao: Pubkey,
 }
 
+impl TransferAuthorityEvent {
+  pub const VERSION: u8 = 1;
+}
+
 #[derive(Accounts)]
 pub struct TransferAuthority<'info> {
-  #[account(mut)]
+  // The authority, or the master signing through an executed proposal
   pub authority: Signer<'info>,
   /// CHECK: Just a pure account
   pub new_authority: AccountInfo<'info>,
-  #[account(mut, has_one = authority)]
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
   pub dao: Account<'info, Dao>,
 }
 
-pub fn exec(ctx: Context<TransferAuthority>) -> Result<()> {
+pub(crate) fn exec(ctx: Context<TransferAuthority>) -> Result<()> {
   let dao = &mut ctx.accounts.dao;
-  dao.authority = ctx.accounts.new_authority.key();
+  // The new authority takes over once it accepts
+  dao.pending_authority = ctx.accounts.new_authority.key();
 
   emit!(TransferAuthorityEvent {
+    version: TransferAuthorityEvent::VERSION,
     authority: ctx.accounts.authority.key(),
-    new_authority: dao.authority,
+    new_authority: dao.pending_authority,
     dao: dao.key()
   });
 
@@ -0,0 +1,48 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct UpdateContentThresholdEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub old_content_threshold: u64,
+  pub new_content_threshold: u64,
+}
+
+impl UpdateContentThresholdEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct UpdateContentThreshold<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<UpdateContentThreshold>, content_threshold: u64) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  // NFT DAOs hold collection items rather than the governance mint
+  if dao.is_nft && content_threshold > 0 {
+    return err!(ErrorCode::InvalidContentThreshold);
+  }
+  let old_content_threshold = dao.content_threshold;
+  dao.content_threshold = content_threshold;
+
+  emit!(UpdateContentThresholdEvent {
+    version: UpdateContentThresholdEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    dao: dao.key(),
+    old_content_threshold,
+    new_content_threshold: dao.content_threshold
+  });
+
+  Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface;
+
+#[event]
+pub struct UpdateDaoCouncilEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub old_council_mint: Pubkey,
+  pub new_council_mint: Pubkey,
+  pub old_council_supply: u64,
+  pub new_council_supply: u64,
+}
+
+impl UpdateDaoCouncilEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct UpdateDaoCouncil<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+  // Omit the council mint to turn the DAO back into a single house
+  pub council_mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
+}
+
+pub(crate) fn exec(ctx: Context<UpdateDaoCouncil>, council_supply: u64) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  let old_council_mint = dao.council_mint;
+  let old_council_supply = dao.council_supply;
+  match &ctx.accounts.council_mint {
+    Some(council_mint) => {
+      // Validate data
+      if dao.is_nft {
+        return err!(ErrorCode::UnsupportedNftCouncil);
+      }
+      if council_mint.key() == dao.mint || council_supply == 0 {
+        return err!(ErrorCode::InvalidCouncilMint);
+      }
+      dao.council_mint = council_mint.key();
+      dao.council_supply = council_supply;
+    }
+    None => {
+      dao.council_mint = Pubkey::default();
+      dao.council_supply = 0;
+    }
+  }
+
+  emit!(UpdateDaoCouncilEvent {
+    version: UpdateDaoCouncilEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    dao: dao.key(),
+    old_council_mint,
+    new_council_mint: dao.council_mint,
+    old_council_supply,
+    new_council_supply: dao.council_supply
+  });
+
+  Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct UpdateDaoGuardiansEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub old_guardians: Vec<Pubkey>,
+  pub new_guardians: Vec<Pubkey>,
+  pub old_veto_threshold: u8,
+  pub new_veto_threshold: u8,
+  pub old_timelock: i64,
+  pub new_timelock: i64,
+}
+
+impl UpdateDaoGuardiansEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct UpdateDaoGuardians<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(
+  ctx: Context<UpdateDaoGuardians>,
+  guardians: Vec<Pubkey>,
+  veto_threshold: u8,
+  timelock: i64,
+) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  let old_guardians = dao.guardians.clone();
+  let old_veto_threshold = dao.veto_threshold;
+  let old_timelock = dao.timelock;
+  // Validate data
+  if guardians.len() > Dao::MAX_GUARDIANS
+    || (veto_threshold as usize) > guardians.len()
+    || (!guardians.is_empty() && veto_threshold == 0)
+    || (1..guardians.len()).any(|i| guardians[..i].contains(&guardians[i]))
+    || timelock < 0
+  {
+    return err!(ErrorCode::InvalidGuardians);
+  }
+
+  dao.guardians = guardians;
+  dao.veto_threshold = veto_threshold;
+  dao.timelock = timelock;
+
+  emit!(UpdateDaoGuardiansEvent {
+    version: UpdateDaoGuardiansEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    dao: dao.key(),
+    old_guardians,
+    new_guardians: dao.guardians.clone(),
+    old_veto_threshold,
+    new_veto_threshold: dao.veto_threshold,
+    old_timelock,
+    new_timelock: dao.timelock
+  });
+
+  Ok(())
+}
@@ -1,27 +1,70 @@
-use crate::schema::dao::*;
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, metadata::*};
+use crate::traits::Permission;
+use crate::utils::is_valid_metadata;
 use anchor_lang::prelude::*;
 
 #[event]
 pub struct UpdateDaoMetadataEvent {
+  pub version: u8,
+  pub authority: Pubkey,
   pub dao: Pubkey,
-  pub metadata: [u8; 32],
+  pub old_metadata: [u8; 32],
+  pub new_metadata: [u8; 32],
+  pub old_hash_algorithm: HashAlgorithm,
+  pub new_hash_algorithm: HashAlgorithm,
+  pub old_metadata_uri: String,
+  pub new_metadata_uri: String,
+}
+
+impl UpdateDaoMetadataEvent {
+  pub const VERSION: u8 = 1;
 }
 
 #[derive(Accounts)]
+#[instruction(metadata: [u8; 32], hash_algorithm: HashAlgorithm, metadata_uri: String)]
 pub struct UpdateDaoMetadata<'info> {
+  // The authority, or the master signing through an executed proposal
   #[account(mut)]
   pub authority: Signer<'info>,
-  #[account(mut, has_one = authority)]
+  #[account(
+    mut,
+    realloc = Dao::LEN + metadata_uri.len(),
+    realloc::payer = authority,
+    realloc::zero = false,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
   pub dao: Account<'info, Dao>,
+  pub system_program: Program<'info, System>,
 }
 
-pub fn exec(ctx: Context<UpdateDaoMetadata>, metadata: [u8; 32]) -> Result<()> {
+pub(crate) fn exec(
+  ctx: Context<UpdateDaoMetadata>,
+  metadata: [u8; 32],
+  hash_algorithm: HashAlgorithm,
+  metadata_uri: String,
+) -> Result<()> {
   let dao = &mut ctx.accounts.dao;
+  // Validate data
+  if !is_valid_metadata(&metadata, hash_algorithm, &metadata_uri) {
+    return err!(ErrorCode::InvalidMetadataUri);
+  }
+  let old_metadata = dao.metadata;
+  let old_hash_algorithm = dao.hash_algorithm;
+  let old_metadata_uri = std::mem::replace(&mut dao.metadata_uri, metadata_uri);
   dao.metadata = metadata;
+  dao.hash_algorithm = hash_algorithm;
 
   emit!(UpdateDaoMetadataEvent {
+    version: UpdateDaoMetadataEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
     dao: dao.key(),
-    metadata: dao.metadata
+    old_metadata,
+    new_metadata: dao.metadata,
+    old_hash_algorithm,
+    new_hash_algorithm: dao.hash_algorithm,
+    old_metadata_uri,
+    new_metadata_uri: dao.metadata_uri.clone()
   });
 
   Ok(())
@@ -1,27 +1,43 @@
+use crate::errors::ErrorCode;
 use crate::schema::dao::*;
+use crate::traits::Permission;
 use anchor_lang::prelude::*;
 
 #[event]
 pub struct UpdateDaoRegimeEvent {
+  pub version: u8,
+  pub authority: Pubkey,
   pub dao: Pubkey,
-  pub regime: DaoRegime,
+  pub old_regime: DaoRegime,
+  pub new_regime: DaoRegime,
+}
+
+impl UpdateDaoRegimeEvent {
+  pub const VERSION: u8 = 1;
 }
 
 #[derive(Accounts)]
 pub struct UpdateDaoRegime<'info> {
-  #[account(mut)]
+  // The authority, or the master signing through an executed proposal
   pub authority: Signer<'info>,
-  #[account(mut, has_one = authority)]
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
   pub dao: Account<'info, Dao>,
 }
 
-pub fn exec(ctx: Context<UpdateDaoRegime>, regime: DaoRegime) -> Result<()> {
+pub(crate) fn exec(ctx: Context<UpdateDaoRegime>, regime: DaoRegime) -> Result<()> {
   let dao = &mut ctx.accounts.dao;
+  let old_regime = dao.regime;
   dao.regime = regime;
 
   emit!(UpdateDaoRegimeEvent {
+    version: UpdateDaoRegimeEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
     dao: dao.key(),
-    regime: dao.regime
+    old_regime,
+    new_regime: dao.regime
   });
 
   Ok(())
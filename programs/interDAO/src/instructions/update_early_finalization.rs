@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct UpdateEarlyFinalizationEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub old_early_finalization: bool,
+  pub new_early_finalization: bool,
+}
+
+impl UpdateEarlyFinalizationEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct UpdateEarlyFinalization<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<UpdateEarlyFinalization>, early_finalization: bool) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  let old_early_finalization = dao.early_finalization;
+  dao.early_finalization = early_finalization;
+
+  emit!(UpdateEarlyFinalizationEvent {
+    version: UpdateEarlyFinalizationEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    dao: dao.key(),
+    old_early_finalization,
+    new_early_finalization: dao.early_finalization
+  });
+
+  Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct UpdateOneDirectionVotingEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub old_one_direction_voting: bool,
+  pub new_one_direction_voting: bool,
+}
+
+impl UpdateOneDirectionVotingEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct UpdateOneDirectionVoting<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(
+  ctx: Context<UpdateOneDirectionVoting>,
+  one_direction_voting: bool,
+) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  let old_one_direction_voting = dao.one_direction_voting;
+  dao.one_direction_voting = one_direction_voting;
+
+  emit!(UpdateOneDirectionVotingEvent {
+    version: UpdateOneDirectionVotingEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    dao: dao.key(),
+    old_one_direction_voting,
+    new_one_direction_voting: dao.one_direction_voting
+  });
+
+  Ok(())
+}
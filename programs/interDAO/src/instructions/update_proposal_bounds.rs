@@ -0,0 +1,86 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct UpdateProposalBoundsEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub old_min_voting_duration: i64,
+  pub new_min_voting_duration: i64,
+  pub old_max_voting_duration: i64,
+  pub new_max_voting_duration: i64,
+  pub old_min_notice_period: i64,
+  pub new_min_notice_period: i64,
+  pub old_execution_window: i64,
+  pub new_execution_window: i64,
+  pub old_retry_window: i64,
+  pub new_retry_window: i64,
+}
+
+impl UpdateProposalBoundsEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct UpdateProposalBounds<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(
+  ctx: Context<UpdateProposalBounds>,
+  min_voting_duration: i64,
+  max_voting_duration: i64,
+  min_notice_period: i64,
+  execution_window: i64,
+  retry_window: i64,
+) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  let old_min_voting_duration = dao.min_voting_duration;
+  let old_max_voting_duration = dao.max_voting_duration;
+  let old_min_notice_period = dao.min_notice_period;
+  let old_execution_window = dao.execution_window;
+  let old_retry_window = dao.retry_window;
+  // Validate data
+  if !Dao::is_valid_proposal_bounds(
+    min_voting_duration,
+    max_voting_duration,
+    min_notice_period,
+    execution_window,
+    retry_window,
+  ) {
+    return err!(ErrorCode::InvalidProposalBounds);
+  }
+
+  dao.min_voting_duration = min_voting_duration;
+  dao.max_voting_duration = max_voting_duration;
+  dao.min_notice_period = min_notice_period;
+  dao.execution_window = execution_window;
+  dao.retry_window = retry_window;
+
+  emit!(UpdateProposalBoundsEvent {
+    version: UpdateProposalBoundsEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    dao: dao.key(),
+    old_min_voting_duration,
+    new_min_voting_duration: dao.min_voting_duration,
+    old_max_voting_duration,
+    new_max_voting_duration: dao.max_voting_duration,
+    old_min_notice_period,
+    new_min_notice_period: dao.min_notice_period,
+    old_execution_window,
+    new_execution_window: dao.execution_window,
+    old_retry_window,
+    new_retry_window: dao.retry_window
+  });
+
+  Ok(())
+}
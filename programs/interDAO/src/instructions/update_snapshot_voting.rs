@@ -0,0 +1,48 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::*;
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct UpdateSnapshotVotingEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub old_snapshot_voting: bool,
+  pub new_snapshot_voting: bool,
+}
+
+impl UpdateSnapshotVotingEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct UpdateSnapshotVoting<'info> {
+  // The authority, or the master signing through an executed proposal
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<UpdateSnapshotVoting>, snapshot_voting: bool) -> Result<()> {
+  let dao = &mut ctx.accounts.dao;
+  // Validate data
+  if snapshot_voting && dao.is_nft {
+    return err!(ErrorCode::UnsupportedNftSnapshotVoting);
+  }
+  let old_snapshot_voting = dao.snapshot_voting;
+  dao.snapshot_voting = snapshot_voting;
+
+  emit!(UpdateSnapshotVotingEvent {
+    version: UpdateSnapshotVotingEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
+    dao: dao.key(),
+    old_snapshot_voting,
+    new_snapshot_voting: dao.snapshot_voting
+  });
+
+  Ok(())
+}
@@ -1,27 +1,43 @@
+use crate::errors::ErrorCode;
 use crate::schema::dao::*;
+use crate::traits::Permission;
 use anchor_lang::prelude::*;
 
 #[event]
 pub struct UpdateSupplyEvent {
-  dao: Pubkey,
-  supply: u64,
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub old_supply: u64,
+  pub new_supply: u64,
+}
+
+impl UpdateSupplyEvent {
+  pub const VERSION: u8 = 1;
 }
 
 #[derive(Accounts)]
 pub struct UpdateSupply<'info> {
-  #[account(mut)]
+  // The authority, or the master signing through an executed proposal
   pub authority: Signer<'info>,
-  #[account(mut, has_one = authority)]
+  #[account(
+    mut,
+    constraint = dao.is_authorized_to_update(authority.key()) @ ErrorCode::NoPermission
+  )]
   pub dao: Account<'info, Dao>,
 }
 
-pub fn exec(ctx: Context<UpdateSupply>, supply: u64) -> Result<()> {
+pub(crate) fn exec(ctx: Context<UpdateSupply>, supply: u64) -> Result<()> {
   let dao = &mut ctx.accounts.dao;
+  let old_supply = dao.supply;
   dao.supply = supply;
 
   emit!(UpdateSupplyEvent {
+    version: UpdateSupplyEvent::VERSION,
+    authority: ctx.accounts.authority.key(),
     dao: dao.key(),
-    supply: dao.supply
+    old_supply,
+    new_supply: dao.supply
   });
 
   Ok(())
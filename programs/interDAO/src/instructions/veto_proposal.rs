@@ -0,0 +1,74 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, proposal::*};
+use crate::traits::Permission;
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct VetoProposalEvent {
+  pub version: u8,
+  pub proposal: Pubkey,
+  pub dao: Pubkey,
+  pub caller: Pubkey,
+  pub vetoes: u8,
+  pub vetoed: bool,
+}
+
+impl VetoProposalEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+  #[account(mut)]
+  pub caller: Signer<'info>,
+  #[account(
+    mut,
+    seeds = [
+      b"proposal".as_ref(),
+      &proposal.index.to_le_bytes(),
+      &dao.key().to_bytes()
+    ],
+    bump,
+    has_one = dao
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+}
+
+pub(crate) fn exec(ctx: Context<VetoProposal>) -> Result<()> {
+  let dao = &ctx.accounts.dao;
+  let proposal = &mut ctx.accounts.proposal;
+  let caller = ctx.accounts.caller.key();
+  // Validate permission & consensus
+  if !dao.is_authorized_to_veto(caller, &proposal.guardians) {
+    return err!(ErrorCode::NoPermission);
+  }
+  match proposal.transition() {
+    ProposalState::Queued => {}
+    ProposalState::Draft | ProposalState::Voting => return err!(ErrorCode::NotEndedProposal),
+    ProposalState::Defeated => return err!(ErrorCode::NotConsentedProposal),
+    ProposalState::Executing | ProposalState::Executed => return err!(ErrorCode::ExecutedProposal),
+    ProposalState::Cancelled => return err!(ErrorCode::VetoedProposal),
+    ProposalState::Succeeded | ProposalState::Expired => return err!(ErrorCode::EndedTimelock),
+  }
+
+  // The authority (when permitted by the regime) vetoes at once
+  let guardian_index = if dao.regime != DaoRegime::Autonomous && dao.authority == caller {
+    None
+  } else {
+    proposal.guardian_index(caller)
+  };
+  proposal.veto(guardian_index).ok_or(ErrorCode::Overflow)?;
+  proposal.transition();
+
+  emit!(VetoProposalEvent {
+    version: VetoProposalEvent::VERSION,
+    proposal: proposal.key(),
+    dao: dao.key(),
+    caller,
+    vetoes: proposal.vetoes,
+    vetoed: proposal.vetoed
+  });
+
+  Ok(())
+}
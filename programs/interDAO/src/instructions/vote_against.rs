@@ -1,36 +1,47 @@
 use crate::errors::ErrorCode;
-use crate::schema::{dao::*, proposal::*, receipt::*};
-use crate::traits::{Age, Consensus};
+use crate::interop::calculate_transfer_fee;
+use crate::schema::{dao::*, proposal::*, receipt::*, voter_record::*};
+use crate::traits::Consensus;
 use anchor_lang::{prelude::*, system_program};
-use anchor_spl::{associated_token, token};
+use anchor_spl::{associated_token, token_interface};
 
 #[event]
 pub struct VoteAgainstEvent {
+  pub version: u8,
   pub authority: Pubkey,
   pub dao: Pubkey,
   pub proposal: Pubkey,
   pub receipt: Pubkey,
+  pub mint: Pubkey,
+  pub action: ReceiptAction,
   pub amount: u64,
+  pub power: u128,
+}
+
+impl VoteAgainstEvent {
+  pub const VERSION: u8 = 1;
 }
 
 #[derive(Accounts)]
-#[instruction(index: u64)]
 pub struct VoteAgainst<'info> {
   #[account(mut)]
   pub authority: Signer<'info>,
   #[account(mut, has_one = mint)]
-  pub src: Box<Account<'info, token::TokenAccount>>,
+  pub src: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
   #[account(seeds = [b"treasurer".as_ref(), &proposal.key().to_bytes()], bump)]
   /// CHECK: Just a pure account
   pub treasurer: AccountInfo<'info>,
-  pub mint: Box<Account<'info, token::Mint>>,
+  pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
   #[account(
-    init_if_needed,
-    payer = authority,
-    associated_token::mint = mint,
-    associated_token::authority = treasurer
+    mut,
+    address = associated_token::get_associated_token_address_with_program_id(
+      &treasurer.key(),
+      &mint.key(),
+      &token_program.key()
+    ) @ ErrorCode::InvalidAssociatedTokenAccount
   )]
-  pub treasury: Box<Account<'info, token::TokenAccount>>,
+  /// CHECK: Created on demand as the associated token account of the treasurer
+  pub treasury: AccountInfo<'info>,
   #[account(
     mut,
     seeds = [
@@ -42,15 +53,27 @@ pub struct VoteAgainst<'info> {
     has_one = dao
   )]
   pub proposal: Account<'info, Proposal>,
-  #[account(has_one = mint)]
   pub dao: Account<'info, Dao>,
+  // Assigns the receipt indices and pins the voting direction when enforced
+  #[account(
+    init_if_needed,
+    payer = authority,
+    space = VoterRecord::LEN,
+    seeds = [
+      b"voter_record".as_ref(),
+      &proposal.key().to_bytes(),
+      &authority.key().to_bytes()
+    ],
+    bump
+  )]
+  pub voter_record: Account<'info, VoterRecord>,
   #[account(
     init,
     payer = authority,
     space = Receipt::LEN,
     seeds = [
       b"receipt".as_ref(),
-      &index.to_le_bytes(),
+      &voter_record.receipts.to_le_bytes(),
       &proposal.key().to_bytes(),
       &authority.key().to_bytes()
     ],
@@ -63,30 +86,39 @@ pub struct VoteAgainst<'info> {
   #[account(mut)]
   /// CHECK: Just a pure account
   pub revenueman: AccountInfo<'info>,
-  pub token_program: Program<'info, token::Token>,
+  pub token_program: Interface<'info, token_interface::TokenInterface>,
   pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
   pub system_program: Program<'info, System>,
   pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn exec(
-  ctx: Context<VoteAgainst>,
-  index: u64,
-  amount: u64,
-  tax: u64,
-  revenue: u64,
-) -> Result<()> {
+pub(crate) fn exec(ctx: Context<VoteAgainst>, amount: u64, tax: u64, revenue: u64) -> Result<()> {
   let receipt = &mut ctx.accounts.receipt;
   let proposal = &mut ctx.accounts.proposal;
+  let mint = ctx.accounts.mint.key();
+  // Validate the voting house
+  if mint != ctx.accounts.dao.mint && proposal.house_of(mint) != House::Council {
+    return err!(ErrorCode::InvalidGovernanceMint);
+  }
   // Validate permission & consensus
-  if proposal.is_executed() {
-    return err!(ErrorCode::ExecutedProposal);
+  match proposal.transition() {
+    ProposalState::Voting => {}
+    ProposalState::Draft => return err!(ErrorCode::NotStartedProposal),
+    ProposalState::Executing | ProposalState::Executed => return err!(ErrorCode::ExecutedProposal),
+    _ => return err!(ErrorCode::EndedProposal),
   }
-  if !proposal.is_started() {
-    return err!(ErrorCode::NotStartedProposal);
+  // Deposited tokens may have been acquired after the snapshot
+  if proposal.snapshot_voting {
+    return err!(ErrorCode::SnapshotVoting);
   }
-  if proposal.is_ended() {
-    return err!(ErrorCode::EndedProposal);
+  let voter_record = &mut ctx.accounts.voter_record;
+  // Enforce one voting direction per voter
+  if proposal.one_direction_voting {
+    voter_record.record(
+      ctx.accounts.authority.key(),
+      proposal.key(),
+      ReceiptAction::VoteAgainst,
+    )?;
   }
 
   // Charge protocol tax
@@ -113,30 +145,52 @@ pub fn exec(
   }
 
   // Init receipt data
-  receipt.index = index;
+  receipt.index = voter_record.next_receipt(ctx.accounts.authority.key(), proposal.key())?;
   receipt.authority = ctx.accounts.authority.key();
   receipt.proposal = proposal.key();
-  // Lock tokens into the treasury
+  receipt.mint = mint;
+  // Create the treasury on demand
+  let create_ctx = CpiContext::new(
+    ctx.accounts.associated_token_program.to_account_info(),
+    associated_token::Create {
+      payer: ctx.accounts.authority.to_account_info(),
+      associated_token: ctx.accounts.treasury.to_account_info(),
+      authority: ctx.accounts.treasurer.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
+      system_program: ctx.accounts.system_program.to_account_info(),
+      token_program: ctx.accounts.token_program.to_account_info(),
+    },
+  );
+  associated_token::create_idempotent(create_ctx)?;
+  // Lock tokens into the treasury (excluding the transfer fee withheld by Token-2022 mints)
+  let fee = calculate_transfer_fee(&ctx.accounts.mint.to_account_info(), amount)
+    .ok_or(ErrorCode::InvalidTransferFee)?;
+  let locked_amount = amount.checked_sub(fee).ok_or(ErrorCode::Overflow)?;
   let transfer_ctx = CpiContext::new(
     ctx.accounts.token_program.to_account_info(),
-    token::Transfer {
+    token_interface::TransferChecked {
       from: ctx.accounts.src.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
       to: ctx.accounts.treasury.to_account_info(),
       authority: ctx.accounts.authority.to_account_info(),
     },
   );
-  token::transfer(transfer_ctx, amount)?;
+  token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
   // Count the votes
   proposal
-    .vote_against(amount, receipt)
+    .vote_against(locked_amount, receipt)
     .ok_or(ErrorCode::Overflow)?;
 
   emit!(VoteAgainstEvent {
+    version: VoteAgainstEvent::VERSION,
     authority: receipt.authority,
     dao: ctx.accounts.dao.key(),
     proposal: receipt.proposal,
     receipt: receipt.key(),
-    amount
+    mint: receipt.mint,
+    action: receipt.action,
+    amount: locked_amount,
+    power: receipt.power,
   });
 
   Ok(())
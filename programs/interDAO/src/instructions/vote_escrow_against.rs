@@ -0,0 +1,167 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, escrow::*, proposal::*, receipt::*, voter_record::*};
+use crate::traits::Consensus;
+use anchor_lang::{prelude::*, system_program};
+
+#[event]
+pub struct VoteEscrowAgainstEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub proposal: Pubkey,
+  pub receipt: Pubkey,
+  pub escrow: Pubkey,
+  pub mint: Pubkey,
+  pub action: ReceiptAction,
+  pub amount: u64,
+  pub power: u128,
+}
+
+impl VoteEscrowAgainstEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct VoteEscrowAgainst<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    seeds = [
+      b"escrow".as_ref(),
+      &dao.key().to_bytes(),
+      &escrow.mint.to_bytes(),
+      &authority.key().to_bytes()
+    ],
+    bump,
+    has_one = authority,
+    has_one = dao
+  )]
+  pub escrow: Account<'info, Escrow>,
+  #[account(
+    mut,
+    seeds = [
+      b"proposal".as_ref(),
+      &proposal.index.to_le_bytes(),
+      &dao.key().to_bytes()
+    ],
+    bump,
+    has_one = dao
+  )]
+  pub proposal: Account<'info, Proposal>,
+  pub dao: Account<'info, Dao>,
+  #[account(
+    init,
+    payer = authority,
+    space = Receipt::LEN,
+    seeds = [
+      b"escrow_receipt".as_ref(),
+      &proposal.key().to_bytes(),
+      &escrow.key().to_bytes()
+    ],
+    bump
+  )]
+  pub receipt: Account<'info, Receipt>,
+  #[account(
+    init_if_needed,
+    payer = authority,
+    space = VoterRecord::LEN,
+    seeds = [
+      b"voter_record".as_ref(),
+      &proposal.key().to_bytes(),
+      &authority.key().to_bytes()
+    ],
+    bump
+  )]
+  pub voter_record: Account<'info, VoterRecord>,
+  #[account(mut)]
+  /// CHECK: Just a pure account
+  pub taxman: AccountInfo<'info>,
+  #[account(mut)]
+  /// CHECK: Just a pure account
+  pub revenueman: AccountInfo<'info>,
+  pub system_program: Program<'info, System>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn exec(ctx: Context<VoteEscrowAgainst>, tax: u64, revenue: u64) -> Result<()> {
+  let receipt = &mut ctx.accounts.receipt;
+  let proposal = &mut ctx.accounts.proposal;
+  let escrow = &mut ctx.accounts.escrow;
+  // Validate the voting house
+  if escrow.mint != ctx.accounts.dao.mint && proposal.house_of(escrow.mint) != House::Council {
+    return err!(ErrorCode::InvalidGovernanceMint);
+  }
+  // Validate permission & consensus
+  match proposal.transition() {
+    ProposalState::Voting => {}
+    ProposalState::Draft => return err!(ErrorCode::NotStartedProposal),
+    ProposalState::Executing | ProposalState::Executed => return err!(ErrorCode::ExecutedProposal),
+    _ => return err!(ErrorCode::EndedProposal),
+  }
+  // Enforce one voting direction per voter
+  if proposal.one_direction_voting {
+    ctx.accounts.voter_record.record(
+      ctx.accounts.authority.key(),
+      proposal.key(),
+      ReceiptAction::VoteAgainst,
+    )?;
+  }
+  // Validate the escrow power, snapshotted at the proposal start
+  let amount = escrow
+    .voting_power_at(proposal.start_date)
+    .ok_or(ErrorCode::Overflow)?;
+  if amount == 0 {
+    return err!(ErrorCode::NoEscrowPower);
+  }
+
+  // Charge protocol tax
+  if tax > 0 {
+    let tax_ctx = CpiContext::new(
+      ctx.accounts.system_program.to_account_info(),
+      system_program::Transfer {
+        from: ctx.accounts.authority.to_account_info(),
+        to: ctx.accounts.taxman.to_account_info(),
+      },
+    );
+    system_program::transfer(tax_ctx, tax)?;
+  }
+  // Charge DAO revenue
+  if revenue > 0 {
+    let revenue_ctx = CpiContext::new(
+      ctx.accounts.system_program.to_account_info(),
+      system_program::Transfer {
+        from: ctx.accounts.authority.to_account_info(),
+        to: ctx.accounts.revenueman.to_account_info(),
+      },
+    );
+    system_program::transfer(revenue_ctx, revenue)?;
+  }
+
+  // Init receipt data
+  receipt.authority = ctx.accounts.authority.key();
+  receipt.proposal = proposal.key();
+  receipt.mint = escrow.mint;
+  receipt.escrow = escrow.key();
+  // Block withdrawals until the vote ends
+  escrow.voting_until = escrow.voting_until.max(proposal.end_date);
+  // Count the votes
+  proposal
+    .vote_against(amount, receipt)
+    .ok_or(ErrorCode::Overflow)?;
+
+  emit!(VoteEscrowAgainstEvent {
+    version: VoteEscrowAgainstEvent::VERSION,
+    authority: receipt.authority,
+    dao: ctx.accounts.dao.key(),
+    proposal: receipt.proposal,
+    receipt: receipt.key(),
+    escrow: receipt.escrow,
+    mint: receipt.mint,
+    action: receipt.action,
+    amount,
+    power: receipt.power,
+  });
+
+  Ok(())
+}
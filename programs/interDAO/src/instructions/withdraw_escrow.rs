@@ -0,0 +1,124 @@
+use crate::errors::ErrorCode;
+use crate::schema::{dao::*, escrow::*};
+use crate::utils::current_timestamp;
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token, token_interface};
+
+#[event]
+pub struct WithdrawEscrowEvent {
+  pub version: u8,
+  pub authority: Pubkey,
+  pub dao: Pubkey,
+  pub escrow: Pubkey,
+  pub mint: Pubkey,
+  pub amount: u64,
+}
+
+impl WithdrawEscrowEvent {
+  pub const VERSION: u8 = 1;
+}
+
+#[derive(Accounts)]
+pub struct WithdrawEscrow<'info> {
+  #[account(mut)]
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    address = associated_token::get_associated_token_address_with_program_id(
+      &authority.key(),
+      &mint.key(),
+      &token_program.key()
+    ) @ ErrorCode::InvalidAssociatedTokenAccount
+  )]
+  /// CHECK: Created on demand as the associated token account of the authority
+  pub dst: AccountInfo<'info>,
+  #[account(seeds = [b"escrow_treasurer".as_ref(), &dao.key().to_bytes()], bump)]
+  /// CHECK: Just a pure account
+  pub escrow_treasurer: AccountInfo<'info>,
+  pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+  #[account(
+    mut,
+    token::mint = mint,
+    token::authority = escrow_treasurer,
+    address = associated_token::get_associated_token_address_with_program_id(
+      &escrow_treasurer.key(),
+      &mint.key(),
+      &token_program.key()
+    ) @ ErrorCode::InvalidAssociatedTokenAccount
+  )]
+  pub escrow_treasury: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+  pub dao: Account<'info, Dao>,
+  #[account(
+    mut,
+    close = authority,
+    seeds = [
+      b"escrow".as_ref(),
+      &dao.key().to_bytes(),
+      &mint.key().to_bytes(),
+      &authority.key().to_bytes()
+    ],
+    bump,
+    has_one = authority,
+    has_one = dao,
+    has_one = mint,
+  )]
+  pub escrow: Account<'info, Escrow>,
+  pub token_program: Interface<'info, token_interface::TokenInterface>,
+  pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+  pub system_program: Program<'info, System>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn exec(ctx: Context<WithdrawEscrow>) -> Result<()> {
+  let escrow = &mut ctx.accounts.escrow;
+  // Validate the lock and live votes
+  let now = current_timestamp().ok_or(ErrorCode::InvalidCurrentDate)?;
+  if !escrow.is_withdrawable(now) {
+    return err!(ErrorCode::LockedEscrow);
+  }
+
+  let amount = escrow.amount;
+  escrow.amount = 0;
+  // Create the destination on demand
+  let create_ctx = CpiContext::new(
+    ctx.accounts.associated_token_program.to_account_info(),
+    associated_token::Create {
+      payer: ctx.accounts.authority.to_account_info(),
+      associated_token: ctx.accounts.dst.to_account_info(),
+      authority: ctx.accounts.authority.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
+      system_program: ctx.accounts.system_program.to_account_info(),
+      token_program: ctx.accounts.token_program.to_account_info(),
+    },
+  );
+  associated_token::create_idempotent(create_ctx)?;
+  // Unlock tokens out of the escrow treasury
+  let seeds: &[&[&[u8]]] = &[&[
+    b"escrow_treasurer".as_ref(),
+    &escrow.dao.to_bytes(),
+    &[*ctx.bumps.get("escrow_treasurer").ok_or(ErrorCode::NoBump)?],
+  ]];
+
+  let transfer_ctx = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    token_interface::TransferChecked {
+      from: ctx.accounts.escrow_treasury.to_account_info(),
+      mint: ctx.accounts.mint.to_account_info(),
+      to: ctx.accounts.dst.to_account_info(),
+      authority: ctx.accounts.escrow_treasurer.to_account_info(),
+    },
+    seeds,
+  );
+  token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+
+  emit!(WithdrawEscrowEvent {
+    version: WithdrawEscrowEvent::VERSION,
+    authority: escrow.authority,
+    dao: escrow.dao,
+    escrow: escrow.key(),
+    mint: escrow.mint,
+    amount
+  });
+
+  Ok(())
+}
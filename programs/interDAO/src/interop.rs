@@ -0,0 +1,44 @@
+use crate::errors::ErrorCode;
+use crate::schema::dao::Dao;
+use anchor_lang::prelude::*;
+use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+use spl_token_2022::extension::{
+  transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+};
+
+///
+/// The fee withheld by a Token-2022 mint with the transfer fee extension.
+/// Classic SPL mints and Token-2022 mints without the extension charge nothing.
+///
+pub fn calculate_transfer_fee(mint: &AccountInfo, amount: u64) -> Option<u64> {
+  if *mint.owner != spl_token_2022::ID {
+    return Some(0);
+  }
+  let data = mint.try_borrow_data().ok()?;
+  let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data).ok()?;
+  match mint.get_extension::<TransferFeeConfig>() {
+    Ok(config) => {
+      let epoch = Clock::get().ok()?.epoch;
+      config.calculate_epoch_fee(epoch, amount)
+    }
+    Err(_) => Some(0),
+  }
+}
+
+///
+/// The NFT belongs to the verified collection of the DAO
+///
+pub fn is_valid_mint_nft(dao: &Dao, mint_nft: Pubkey, metadata: &AccountInfo) -> Result<bool> {
+  if *metadata.owner != mpl_token_metadata::ID {
+    return err!(ErrorCode::InvalidNftMetadata);
+  }
+  let metadata: Metadata =
+    Metadata::from_account_info(metadata).map_err(|_| ErrorCode::InvalidNftMetadata)?;
+  let is_valid = match metadata.collection {
+    Some(collection) => {
+      dao.mint == collection.key && collection.verified && mint_nft == metadata.mint
+    }
+    None => false,
+  };
+  Ok(is_valid)
+}
@@ -1,18 +1,28 @@
+#![allow(
+  clippy::result_large_err,
+  clippy::too_many_arguments,
+  clippy::derivable_impls
+)]
 use anchor_lang::prelude::*;
 
-pub mod constants;
-pub mod errors;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod instructions;
-pub mod schema;
-pub mod traits;
-pub mod utils;
+pub mod interop;
 
-pub use errors::*;
+// The IDL generator of anchor 0.28 only reads the sources of this crate, so the shared
+// modules are linked into src for it and left out of the build
+#[cfg(any())]
+mod errors;
+#[cfg(any())]
+mod schema;
+
+pub use inter_dao_interface::{check_id, constants, errors, id, schema, traits, utils, ID};
+
+pub use errors::ErrorCode;
 pub use instructions::*;
 pub use schema::*;
 
-declare_id!("BND6UZZG2rLGtaYLioBtXFnrBtvtp5g6YXWKEc4LLqrJ");
-
 #[program]
 pub mod inter_dao {
   use super::*;
@@ -22,10 +32,31 @@ pub mod inter_dao {
     regime: DaoRegime,
     supply: u64,
     metadata: [u8; 32],
+    hash_algorithm: HashAlgorithm,
+    metadata_uri: String,
     is_nft: bool,
     is_public: bool,
+    min_voting_duration: i64,
+    max_voting_duration: i64,
+    min_notice_period: i64,
+    execution_window: i64,
+    retry_window: i64,
   ) -> Result<()> {
-    initialize_dao::exec(ctx, regime, supply, metadata, is_nft, is_public)
+    initialize_dao::exec(
+      ctx,
+      regime,
+      supply,
+      metadata,
+      hash_algorithm,
+      metadata_uri,
+      is_nft,
+      is_public,
+      min_voting_duration,
+      max_voting_duration,
+      min_notice_period,
+      execution_window,
+      retry_window,
+    )
   }
 
   pub fn initialize_proposal(
@@ -35,6 +66,9 @@ pub mod inter_dao {
     start_date: i64,
     end_date: i64,
     metadata: [u8; 32],
+    hash_algorithm: HashAlgorithm,
+    metadata_uri: String,
+    independent_instructions: bool,
     tax: u64,
     revenue: u64,
   ) -> Result<()> {
@@ -45,6 +79,9 @@ pub mod inter_dao {
       start_date,
       end_date,
       metadata,
+      hash_algorithm,
+      metadata_uri,
+      independent_instructions,
       tax,
       revenue,
     )
@@ -70,43 +107,122 @@ pub mod inter_dao {
     )
   }
 
-  pub fn vote_for(
-    ctx: Context<VoteFor>,
-    index: u64,
+  pub fn initialize_proposal_template(
+    ctx: Context<InitializeProposalTemplate>,
+    tx_idx: u8,
+    kind: ProposalInstructionKind,
+  ) -> Result<()> {
+    initialize_proposal_template::exec(ctx, tx_idx, kind)
+  }
+
+  pub fn amend_proposal(
+    ctx: Context<AmendProposal>,
+    consensus_mechanism: ConsensusMechanism,
+    consesus_quorum: ConsensusQuorum,
+    start_date: i64,
+    end_date: i64,
+    metadata: [u8; 32],
+    hash_algorithm: HashAlgorithm,
+    metadata_uri: String,
+  ) -> Result<()> {
+    amend_proposal::exec(
+      ctx,
+      consensus_mechanism,
+      consesus_quorum,
+      start_date,
+      end_date,
+      metadata,
+      hash_algorithm,
+      metadata_uri,
+    )
+  }
+
+  pub fn replace_proposal_instruction(
+    ctx: Context<ReplaceProposalInstruction>,
+    data: Vec<u8>,
+    pubkeys: Vec<Pubkey>,
+    is_signers: Vec<bool>,
+    is_writables: Vec<bool>,
+    is_masters: Vec<bool>,
+    tx_idx: u8,
+  ) -> Result<()> {
+    replace_proposal_instruction::exec(
+      ctx,
+      tx_idx,
+      data,
+      pubkeys,
+      is_signers,
+      is_writables,
+      is_masters,
+    )
+  }
+
+  pub fn remove_proposal_instruction(ctx: Context<RemoveProposalInstruction>) -> Result<()> {
+    remove_proposal_instruction::exec(ctx)
+  }
+
+  pub fn vote_for(ctx: Context<VoteFor>, amount: u64, tax: u64, revenue: u64) -> Result<()> {
+    vote_for::exec(ctx, amount, tax, revenue)
+  }
+
+  pub fn vote_nft_for(ctx: Context<VoteNftFor>, tax: u64, revenue: u64) -> Result<()> {
+    vote_nft_for::exec(ctx, tax, revenue)
+  }
+
+  pub fn vote_against(
+    ctx: Context<VoteAgainst>,
     amount: u64,
     tax: u64,
     revenue: u64,
   ) -> Result<()> {
-    vote_for::exec(ctx, index, amount, tax, revenue)
+    vote_against::exec(ctx, amount, tax, revenue)
   }
 
-  pub fn vote_nft_for(ctx: Context<VoteNftFor>, index: u64, tax: u64, revenue: u64) -> Result<()> {
-    vote_nft_for::exec(ctx, index, tax, revenue)
+  pub fn vote_nft_against(ctx: Context<VoteNftAgainst>, tax: u64, revenue: u64) -> Result<()> {
+    vote_nft_against::exec(ctx, tax, revenue)
   }
 
-  pub fn vote_against(
-    ctx: Context<VoteAgainst>,
-    index: u64,
+  pub fn increase_vote(
+    ctx: Context<IncreaseVote>,
     amount: u64,
     tax: u64,
     revenue: u64,
   ) -> Result<()> {
-    vote_against::exec(ctx, index, amount, tax, revenue)
+    increase_vote::exec(ctx, amount, tax, revenue)
   }
 
-  pub fn vote_nft_against(
-    ctx: Context<VoteNftAgainst>,
-    index: u64,
+  pub fn lock_escrow(ctx: Context<LockEscrow>, amount: u64, unlocked_date: i64) -> Result<()> {
+    lock_escrow::exec(ctx, amount, unlocked_date)
+  }
+
+  pub fn withdraw_escrow(ctx: Context<WithdrawEscrow>) -> Result<()> {
+    withdraw_escrow::exec(ctx)
+  }
+
+  pub fn vote_escrow_for(ctx: Context<VoteEscrowFor>, tax: u64, revenue: u64) -> Result<()> {
+    vote_escrow_for::exec(ctx, tax, revenue)
+  }
+
+  pub fn vote_escrow_against(
+    ctx: Context<VoteEscrowAgainst>,
     tax: u64,
     revenue: u64,
   ) -> Result<()> {
-    vote_nft_against::exec(ctx, index, tax, revenue)
+    vote_escrow_against::exec(ctx, tax, revenue)
   }
 
   pub fn execute_proposal_instruction(ctx: Context<ExecuteProposalInstruction>) -> Result<()> {
     execute_proposal_instruction::exec(ctx)
   }
 
+  pub fn fail_proposal_instruction(ctx: Context<FailProposalInstruction>) -> Result<()> {
+    fail_proposal_instruction::exec(ctx)
+  }
+
+  pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+    finalize_proposal::exec(ctx)
+  }
+
   pub fn close(ctx: Context<Close>) -> Result<()> {
     close::exec(ctx)
   }
@@ -115,12 +231,21 @@ pub mod inter_dao {
     close_nft_voting::exec(ctx)
   }
 
+  pub fn close_escrow_voting(ctx: Context<CloseEscrowVoting>) -> Result<()> {
+    close_escrow_voting::exec(ctx)
+  }
+
   pub fn update_dao_regime(ctx: Context<UpdateDaoRegime>, regime: DaoRegime) -> Result<()> {
     update_dao_regime::exec(ctx, regime)
   }
 
-  pub fn update_dao_metadata(ctx: Context<UpdateDaoMetadata>, metadata: [u8; 32]) -> Result<()> {
-    update_dao_metadata::exec(ctx, metadata)
+  pub fn update_dao_metadata(
+    ctx: Context<UpdateDaoMetadata>,
+    metadata: [u8; 32],
+    hash_algorithm: HashAlgorithm,
+    metadata_uri: String,
+  ) -> Result<()> {
+    update_dao_metadata::exec(ctx, metadata, hash_algorithm, metadata_uri)
   }
 
   pub fn update_supply(ctx: Context<UpdateSupply>, supply: u64) -> Result<()> {
@@ -131,11 +256,105 @@ pub mod inter_dao {
     transfer_authority::exec(ctx)
   }
 
+  pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    accept_authority::exec(ctx)
+  }
+
+  pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    cancel_authority_transfer::exec(ctx)
+  }
+
+  pub fn renounce_authority(ctx: Context<RenounceAuthority>) -> Result<()> {
+    renounce_authority::exec(ctx)
+  }
+
+  pub fn update_proposal_bounds(
+    ctx: Context<UpdateProposalBounds>,
+    min_voting_duration: i64,
+    max_voting_duration: i64,
+    min_notice_period: i64,
+    execution_window: i64,
+    retry_window: i64,
+  ) -> Result<()> {
+    update_proposal_bounds::exec(
+      ctx,
+      min_voting_duration,
+      max_voting_duration,
+      min_notice_period,
+      execution_window,
+      retry_window,
+    )
+  }
+
+  pub fn update_early_finalization(
+    ctx: Context<UpdateEarlyFinalization>,
+    early_finalization: bool,
+  ) -> Result<()> {
+    update_early_finalization::exec(ctx, early_finalization)
+  }
+
+  pub fn update_one_direction_voting(
+    ctx: Context<UpdateOneDirectionVoting>,
+    one_direction_voting: bool,
+  ) -> Result<()> {
+    update_one_direction_voting::exec(ctx, one_direction_voting)
+  }
+
+  pub fn update_snapshot_voting(
+    ctx: Context<UpdateSnapshotVoting>,
+    snapshot_voting: bool,
+  ) -> Result<()> {
+    update_snapshot_voting::exec(ctx, snapshot_voting)
+  }
+
+  pub fn update_dao_guardians(
+    ctx: Context<UpdateDaoGuardians>,
+    guardians: Vec<Pubkey>,
+    veto_threshold: u8,
+    timelock: i64,
+  ) -> Result<()> {
+    update_dao_guardians::exec(ctx, guardians, veto_threshold, timelock)
+  }
+
+  pub fn update_dao_council(ctx: Context<UpdateDaoCouncil>, council_supply: u64) -> Result<()> {
+    update_dao_council::exec(ctx, council_supply)
+  }
+
+  pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+    veto_proposal::exec(ctx)
+  }
+
+  pub fn get_proposal_status(ctx: Context<GetProposalStatus>) -> Result<ProposalStatus> {
+    get_proposal_status::exec(ctx)
+  }
+
   pub fn initialize_content(
     ctx: Context<InitializeContent>,
     discriminator: [u8; 8],
     metadata: [u8; 32],
+    hash_algorithm: HashAlgorithm,
+    metadata_uri: String,
+  ) -> Result<()> {
+    initialize_content::exec(ctx, discriminator, metadata, hash_algorithm, metadata_uri)
+  }
+
+  pub fn edit_content(
+    ctx: Context<EditContent>,
+    metadata: [u8; 32],
+    hash_algorithm: HashAlgorithm,
+    metadata_uri: String,
+  ) -> Result<()> {
+    edit_content::exec(ctx, metadata, hash_algorithm, metadata_uri)
+  }
+
+  pub fn delete_content(ctx: Context<DeleteContent>) -> Result<()> {
+    delete_content::exec(ctx)
+  }
+
+  pub fn update_content_threshold(
+    ctx: Context<UpdateContentThreshold>,
+    content_threshold: u64,
   ) -> Result<()> {
-    initialize_content::exec(ctx, discriminator, metadata)
+    update_content_threshold::exec(ctx, content_threshold)
   }
 }
@@ -0,0 +1,735 @@
+//! The governance flows driven by the client builders. The program verifies every seed and
+//! account constraint, so a builder drifting from the program fails here.
+mod common;
+
+use anchor_lang::{
+  prelude::*,
+  solana_program::{entrypoint::ProgramResult, instruction::Instruction, system_instruction},
+};
+use anchor_spl::associated_token;
+use common::{Bank, SOL};
+use inter_dao::{
+  client::{accounts, instructions, pda},
+  ConsensusMechanism, ConsensusQuorum, DaoRegime, Escrow, HashAlgorithm, ProposalInstruction,
+  ProposalInstructionKind, ProposalState,
+};
+
+const SUPPLY: u64 = 1_000;
+const NOTICE: i64 = 10;
+const VOTING_DURATION: i64 = 100;
+const EXECUTION_WINDOW: i64 = 3_600;
+const RETRY_WINDOW: i64 = 600;
+const TRANSFERRED: u64 = SOL / 2;
+
+struct Client {
+  bank: Bank,
+  authority: Pubkey,
+  voter: Pubkey,
+  recipient: Pubkey,
+  mint: Pubkey,
+  token_program: Pubkey,
+  dao: Pubkey,
+  master: Pubkey,
+}
+
+impl Client {
+  ///
+  /// An autonomous public DAO governed by the mint, with a funded master
+  ///
+  fn new() -> Self {
+    let mut bank = Bank::new();
+    let (authority, voter) = (Pubkey::new_unique(), Pubkey::new_unique());
+    bank.airdrop(&authority, 10 * SOL);
+    bank.airdrop(&voter, 10 * SOL);
+    let mint = bank.create_mint(&authority, SUPPLY, 0);
+    let token_program = bank.account(&mint).unwrap().owner;
+    bank.create_token_account(&mint, &voter, SUPPLY * 3 / 5);
+
+    let dao = Pubkey::new_unique();
+    bank
+      .process(instructions::initialize_dao(
+        authority,
+        dao,
+        mint,
+        DaoRegime::Autonomous,
+        SUPPLY,
+        [0; 32],
+        HashAlgorithm::Sha256,
+        String::new(),
+        false,
+        true,
+        60,
+        86_400,
+        NOTICE,
+        EXECUTION_WINDOW,
+        RETRY_WINDOW,
+      ))
+      .unwrap();
+    let (master, _) = pda::master(&dao);
+    bank.airdrop(&master, 10 * SOL);
+
+    Client {
+      bank,
+      authority,
+      voter,
+      recipient: Pubkey::new_unique(),
+      mint,
+      token_program,
+      dao,
+      master,
+    }
+  }
+
+  fn dao(&self) -> inter_dao::Dao {
+    accounts::decode_dao(&self.bank.account(&self.dao).unwrap().data).unwrap()
+  }
+
+  fn proposal(&self, proposal: &Pubkey) -> inter_dao::Proposal {
+    accounts::decode_proposal(&self.bank.account(proposal).unwrap().data).unwrap()
+  }
+
+  fn receipts(&self, proposal: &Pubkey) -> u64 {
+    let (voter_record, _) = pda::voter_record(proposal, &self.voter);
+    match self.bank.account(&voter_record) {
+      Some(account) => {
+        accounts::decode_voter_record(&account.data)
+          .unwrap()
+          .receipts
+      }
+      None => 0,
+    }
+  }
+
+  ///
+  /// A proposal at the current DAO nonce, starting after the notice period
+  ///
+  fn initialize_proposal(&mut self, independent_instructions: bool) -> (Pubkey, i64, i64) {
+    let nonce = self.dao().nonce;
+    let start_date = self.bank.now() + NOTICE;
+    let end_date = start_date + VOTING_DURATION;
+    self
+      .bank
+      .process(instructions::initialize_proposal(
+        self.authority,
+        self.dao,
+        nonce,
+        self.authority,
+        self.authority,
+        ConsensusMechanism::StakedTokenCounter,
+        ConsensusQuorum::Half,
+        start_date,
+        end_date,
+        [0; 32],
+        HashAlgorithm::Sha256,
+        String::new(),
+        independent_instructions,
+        0,
+        0,
+      ))
+      .unwrap();
+    (pda::proposal(&self.dao, nonce).0, start_date, end_date)
+  }
+
+  fn add_instruction(&mut self, proposal: Pubkey, ix: &Instruction) -> Pubkey {
+    let proposal_instruction = Pubkey::new_unique();
+    let tx_idx = self.proposal(&proposal).total_instruction;
+    self
+      .bank
+      .process(instructions::initialize_proposal_instruction(
+        self.authority,
+        proposal_instruction,
+        proposal,
+        self.dao,
+        ix,
+        tx_idx,
+      ))
+      .unwrap();
+    proposal_instruction
+  }
+
+  fn vote_for(&mut self, proposal: Pubkey, amount: u64) -> ProgramResult {
+    let receipts = self.receipts(&proposal);
+    self.bank.process(instructions::vote_for(
+      self.voter,
+      proposal,
+      self.dao,
+      self.mint,
+      self.token_program,
+      self.authority,
+      self.authority,
+      receipts,
+      amount,
+      0,
+      0,
+    ))
+  }
+
+  fn execute(&mut self, proposal: Pubkey, proposal_instruction: Pubkey) -> ProgramResult {
+    let stored: ProposalInstruction = self.bank.get(&proposal_instruction);
+    self
+      .bank
+      .process(instructions::execute_proposal_instruction(
+        self.voter,
+        proposal,
+        self.dao,
+        proposal_instruction,
+        &stored,
+      ))
+  }
+}
+
+#[test]
+fn test_proposal_lifecycle() {
+  let mut client = Client::new();
+  let (authority, voter, recipient, master) = (
+    client.authority,
+    client.voter,
+    client.recipient,
+    client.master,
+  );
+  let (mint, token_program, dao) = (client.mint, client.token_program, client.dao);
+  let (proposal, start_date, end_date) = client.initialize_proposal(false);
+  assert_eq!(client.dao().nonce, 1);
+
+  // Drafts are edited before the start date
+  let first = Pubkey::new_unique();
+  client
+    .bank
+    .process(instructions::initialize_proposal_template(
+      authority,
+      first,
+      proposal,
+      dao,
+      None,
+      0,
+      ProposalInstructionKind::SolTransfer {
+        dst: recipient,
+        lamports: TRANSFERRED,
+      },
+    ))
+    .unwrap();
+  let transfer = system_instruction::transfer(&master, &recipient, TRANSFERRED);
+  let removed = client.add_instruction(proposal, &transfer);
+  let last = client.add_instruction(proposal, &transfer);
+  client
+    .bank
+    .process(instructions::replace_proposal_instruction(
+      authority,
+      last,
+      proposal,
+      dao,
+      &system_instruction::transfer(&master, &recipient, TRANSFERRED / 2),
+      2,
+    ))
+    .unwrap();
+  client
+    .bank
+    .process(instructions::remove_proposal_instruction(
+      authority,
+      removed,
+      proposal,
+      dao,
+      &[last],
+    ))
+    .unwrap();
+  assert_eq!(client.proposal(&proposal).total_instruction, 2);
+  let stored: ProposalInstruction = client.bank.get(&last);
+  assert_eq!(stored.index, 1);
+  assert!(stored.accounts[0].is_master);
+  client
+    .bank
+    .process(instructions::amend_proposal(
+      authority,
+      proposal,
+      dao,
+      ConsensusMechanism::StakedTokenCounter,
+      ConsensusQuorum::Half,
+      start_date,
+      end_date,
+      [1; 32],
+      HashAlgorithm::Sha256,
+      String::new(),
+    ))
+    .unwrap();
+  assert_eq!(client.proposal(&proposal).metadata, [1; 32]);
+
+  // Receipts follow the counter of the voter record
+  client.bank.warp_to(start_date);
+  client.vote_for(proposal, SUPPLY / 2).unwrap();
+  client
+    .bank
+    .process(instructions::increase_vote(
+      voter,
+      proposal,
+      dao,
+      mint,
+      token_program,
+      authority,
+      authority,
+      0,
+      SUPPLY / 20,
+      0,
+      0,
+    ))
+    .unwrap();
+  client
+    .bank
+    .process(instructions::vote_against(
+      voter,
+      proposal,
+      dao,
+      mint,
+      token_program,
+      authority,
+      authority,
+      client.receipts(&proposal),
+      SUPPLY / 50,
+      0,
+      0,
+    ))
+    .unwrap();
+  let receipts = pda::receipts(&proposal, &voter, client.receipts(&proposal));
+  assert_eq!(receipts.len(), 2);
+  let amounts: Vec<u64> = receipts
+    .iter()
+    .map(|receipt| {
+      accounts::decode_receipt(&client.bank.account(receipt).unwrap().data)
+        .unwrap()
+        .amount
+    })
+    .collect();
+  assert_eq!(amounts, vec![SUPPLY / 2 + SUPPLY / 20, SUPPLY / 50]);
+  assert_eq!(
+    client
+      .bank
+      .token_balance(&pda::treasury(&proposal, &mint, &token_program)),
+    SUPPLY / 2 + SUPPLY / 20 + SUPPLY / 50
+  );
+
+  client
+    .bank
+    .process(instructions::get_proposal_status(proposal, dao))
+    .unwrap();
+  let (program_id, return_data) = client.bank.return_data().unwrap();
+  assert_eq!(program_id, inter_dao::ID);
+  let status = accounts::decode_proposal_status(&return_data).unwrap();
+  assert_eq!(status.proposal, proposal);
+  assert_eq!(status.state, ProposalState::Voting);
+
+  // The invoked accounts are appended from the stored instructions
+  client.bank.warp_to(end_date + 1);
+  client
+    .bank
+    .process(instructions::finalize_proposal(voter, proposal, dao))
+    .unwrap();
+  assert_eq!(client.proposal(&proposal).state, ProposalState::Succeeded);
+  client.execute(proposal, first).unwrap();
+  client.execute(proposal, last).unwrap();
+  assert_eq!(client.proposal(&proposal).state, ProposalState::Executed);
+  assert_eq!(
+    client.bank.lamports(&recipient),
+    TRANSFERRED + TRANSFERRED / 2
+  );
+
+  for index in 0..2 {
+    client
+      .bank
+      .process(instructions::close(
+        voter,
+        proposal,
+        dao,
+        mint,
+        token_program,
+        index,
+      ))
+      .unwrap();
+  }
+  let src =
+    associated_token::get_associated_token_address_with_program_id(&voter, &mint, &token_program);
+  assert_eq!(client.bank.token_balance(&src), SUPPLY * 3 / 5);
+}
+
+#[test]
+fn test_failed_instruction_and_veto() {
+  let mut client = Client::new();
+  let (authority, voter, recipient, master, dao) = (
+    client.authority,
+    client.voter,
+    client.recipient,
+    client.master,
+    client.dao,
+  );
+  let guardian = Pubkey::new_unique();
+  let timelock = 50;
+  client
+    .bank
+    .process(instructions::update_dao_guardians(
+      authority,
+      dao,
+      vec![guardian],
+      1,
+      timelock,
+    ))
+    .unwrap();
+  // The voter alone passes both proposals
+  client
+    .bank
+    .process(instructions::update_supply(authority, dao, SUPPLY / 2))
+    .unwrap();
+
+  // The master can't fund the transfer, so the instruction is failed after the retry window
+  let (failing, start_date, end_date) = client.initialize_proposal(true);
+  let proposal_instruction = client.add_instruction(
+    failing,
+    &system_instruction::transfer(&master, &recipient, 100 * SOL),
+  );
+  let (vetoed, _, _) = client.initialize_proposal(false);
+  client.add_instruction(
+    vetoed,
+    &system_instruction::transfer(&master, &recipient, TRANSFERRED),
+  );
+  client.bank.warp_to(start_date);
+  client.vote_for(failing, SUPPLY * 3 / 10).unwrap();
+  client.vote_for(vetoed, SUPPLY * 3 / 10).unwrap();
+  client.bank.warp_to(end_date + 1);
+
+  client
+    .bank
+    .process(instructions::veto_proposal(guardian, vetoed, dao))
+    .unwrap();
+  assert_eq!(client.proposal(&vetoed).state, ProposalState::Cancelled);
+
+  client.bank.warp_to(end_date + timelock + 1);
+  assert!(client.execute(failing, proposal_instruction).is_err());
+  client.bank.warp(RETRY_WINDOW + 1);
+  client
+    .bank
+    .process(instructions::fail_proposal_instruction(
+      voter,
+      failing,
+      dao,
+      proposal_instruction,
+    ))
+    .unwrap();
+  let proposal = client.proposal(&failing);
+  assert_eq!(proposal.total_failed, 1);
+  assert_eq!(proposal.state, ProposalState::Executed);
+}
+
+#[test]
+fn test_escrow_voting() {
+  let mut client = Client::new();
+  let (authority, voter, mint, token_program, dao) = (
+    client.authority,
+    client.voter,
+    client.mint,
+    client.token_program,
+    client.dao,
+  );
+  let unlocked_date = client.bank.now() + Escrow::MAX_LOCK_DURATION;
+  client
+    .bank
+    .process(instructions::lock_escrow(
+      voter,
+      dao,
+      mint,
+      token_program,
+      SUPPLY * 3 / 5,
+      unlocked_date,
+    ))
+    .unwrap();
+  let (escrow, _) = pda::escrow(&dao, &mint, &voter);
+  let escrow_data = accounts::decode_escrow(&client.bank.account(&escrow).unwrap().data).unwrap();
+  assert_eq!(
+    client
+      .bank
+      .token_balance(&pda::escrow_treasury(&dao, &mint, &token_program)),
+    SUPPLY * 3 / 5
+  );
+
+  let (supported, start_date, end_date) = client.initialize_proposal(false);
+  let (opposed, _, _) = client.initialize_proposal(false);
+  client.bank.warp_to(start_date);
+  client
+    .bank
+    .process(instructions::vote_escrow_for(
+      voter, supported, dao, mint, authority, authority, 0, 0,
+    ))
+    .unwrap();
+  client
+    .bank
+    .process(instructions::vote_escrow_against(
+      voter, opposed, dao, mint, authority, authority, 0, 0,
+    ))
+    .unwrap();
+  let power = u128::from(escrow_data.voting_power_at(start_date).unwrap());
+  assert_eq!(client.proposal(&supported).voting_for_power, power);
+  assert_eq!(client.proposal(&opposed).voting_against_power, power);
+
+  client.bank.warp_to(end_date + 1);
+  for proposal in [supported, opposed] {
+    client
+      .bank
+      .process(instructions::close_escrow_voting(
+        voter, proposal, dao, mint,
+      ))
+      .unwrap();
+    let (receipt, _) = pda::escrow_receipt(&proposal, &escrow);
+    assert!(client.bank.account(&receipt).is_none());
+  }
+  client.bank.warp_to(unlocked_date);
+  client
+    .bank
+    .process(instructions::withdraw_escrow(
+      voter,
+      dao,
+      mint,
+      token_program,
+    ))
+    .unwrap();
+  assert!(client.bank.account(&escrow).is_none());
+}
+
+#[test]
+fn test_dao_settings() {
+  let mut client = Client::new();
+  let (authority, dao, master) = (client.authority, client.dao, client.master);
+  let council = client.bank.create_mint(&authority, 100, 0);
+  for ix in [
+    instructions::update_supply(authority, dao, SUPPLY * 2),
+    instructions::update_dao_metadata(
+      authority,
+      dao,
+      [1; 32],
+      HashAlgorithm::Sha256,
+      "https://interdao.so/dao.json".to_string(),
+    ),
+    instructions::update_proposal_bounds(authority, dao, 120, 86_400, 0, 0, RETRY_WINDOW),
+    instructions::update_early_finalization(authority, dao, true),
+    instructions::update_one_direction_voting(authority, dao, true),
+    instructions::update_snapshot_voting(authority, dao, true),
+    instructions::update_dao_council(authority, dao, Some(council), 100),
+    instructions::update_content_threshold(authority, dao, 1),
+  ] {
+    client.bank.process(ix).unwrap();
+  }
+  let dao_data = client.dao();
+  assert_eq!(dao_data.supply, SUPPLY * 2);
+  assert_eq!(dao_data.metadata_uri, "https://interdao.so/dao.json");
+  assert_eq!(dao_data.min_voting_duration, 120);
+  assert!(dao_data.early_finalization && dao_data.one_direction_voting && dao_data.snapshot_voting);
+  assert_eq!(
+    (dao_data.council_mint, dao_data.council_supply),
+    (council, 100)
+  );
+  assert_eq!(dao_data.content_threshold, 1);
+  client
+    .bank
+    .process(instructions::update_dao_council(authority, dao, None, 0))
+    .unwrap();
+  assert_eq!(client.dao().council_mint, Pubkey::default());
+
+  let new_authority = Pubkey::new_unique();
+  client
+    .bank
+    .process(instructions::transfer_authority(
+      authority,
+      dao,
+      new_authority,
+    ))
+    .unwrap();
+  client
+    .bank
+    .process(instructions::cancel_authority_transfer(authority, dao))
+    .unwrap();
+  client
+    .bank
+    .process(instructions::transfer_authority(
+      authority,
+      dao,
+      new_authority,
+    ))
+    .unwrap();
+  client
+    .bank
+    .process(instructions::accept_authority(new_authority, dao))
+    .unwrap();
+  assert_eq!(client.dao().authority, new_authority);
+  client
+    .bank
+    .process(instructions::update_dao_regime(
+      new_authority,
+      dao,
+      DaoRegime::Democratic,
+    ))
+    .unwrap();
+  client
+    .bank
+    .process(instructions::update_dao_regime(
+      new_authority,
+      dao,
+      DaoRegime::Autonomous,
+    ))
+    .unwrap();
+  client
+    .bank
+    .process(instructions::renounce_authority(new_authority, dao))
+    .unwrap();
+  assert_eq!(client.dao().authority, master);
+}
+
+#[test]
+fn test_content() {
+  let mut client = Client::new();
+  let (authority, voter, dao) = (client.authority, client.voter, client.dao);
+  let (proposal, _, _) = client.initialize_proposal(false);
+  let post = |author, nonce, parent| {
+    instructions::initialize_content(
+      author,
+      nonce,
+      [1; 8],
+      [0; 32],
+      HashAlgorithm::Sha256,
+      String::new(),
+      dao,
+      Some(proposal),
+      parent,
+      None,
+    )
+  };
+  client.bank.process(post(authority, 0, None)).unwrap();
+  let (topic, _) = pda::content(&dao, 0);
+  client.bank.process(post(voter, 1, Some(topic))).unwrap();
+  let (reply, _) = pda::content(&dao, 1);
+  let content = accounts::decode_content(&client.bank.account(&reply).unwrap().data).unwrap();
+  assert_eq!(content.parent, topic);
+  assert_eq!(content.proposal, proposal);
+
+  client
+    .bank
+    .process(instructions::edit_content(
+      voter,
+      reply,
+      [2; 32],
+      HashAlgorithm::Sha256,
+      "https://interdao.so/reply.json".to_string(),
+    ))
+    .unwrap();
+  let content = accounts::decode_content(&client.bank.account(&reply).unwrap().data).unwrap();
+  assert_eq!(content.revision, 1);
+  client
+    .bank
+    .process(instructions::delete_content(voter, reply))
+    .unwrap();
+  assert!(client.bank.account(&reply).is_none());
+}
+
+///
+/// The bank can't run NFT votes without a metadata program, so their accounts are checked
+/// against the seeds of the program instead
+///
+#[test]
+fn test_nft_voting_accounts() {
+  let (authority, proposal, dao, mint, metadata, taxman) = (
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+  );
+  let token_program = anchor_spl::token::ID;
+  let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &inter_dao::ID).0;
+  let treasurer = find(&[b"treasurer", proposal.as_ref()]);
+  let ata = |owner: &Pubkey| {
+    associated_token::get_associated_token_address_with_program_id(owner, &mint, &token_program)
+  };
+  let receipt = find(&[
+    b"receipt",
+    &3u64.to_le_bytes(),
+    proposal.as_ref(),
+    authority.as_ref(),
+  ]);
+  let voter_record = find(&[b"voter_record", proposal.as_ref(), authority.as_ref()]);
+  let pubkeys = |ix: Instruction| -> Vec<Pubkey> {
+    assert_eq!(ix.program_id, inter_dao::ID);
+    ix.accounts.iter().map(|meta| meta.pubkey).collect()
+  };
+
+  let vote = vec![
+    authority,
+    ata(&authority),
+    treasurer,
+    mint,
+    metadata,
+    ata(&treasurer),
+    proposal,
+    dao,
+    voter_record,
+    receipt,
+    taxman,
+    taxman,
+    token_program,
+    associated_token::ID,
+    anchor_lang::system_program::ID,
+    anchor_lang::solana_program::sysvar::rent::ID,
+  ];
+  assert_eq!(
+    pubkeys(instructions::vote_nft_for(
+      authority,
+      proposal,
+      dao,
+      mint,
+      metadata,
+      token_program,
+      taxman,
+      taxman,
+      3,
+      0,
+      0
+    )),
+    vote
+  );
+  assert_eq!(
+    pubkeys(instructions::vote_nft_against(
+      authority,
+      proposal,
+      dao,
+      mint,
+      metadata,
+      token_program,
+      taxman,
+      taxman,
+      3,
+      0,
+      0
+    )),
+    vote
+  );
+  assert_eq!(
+    pubkeys(instructions::close_nft_voting(
+      authority,
+      proposal,
+      dao,
+      mint,
+      metadata,
+      token_program,
+      3
+    )),
+    vec![
+      authority,
+      ata(&authority),
+      treasurer,
+      mint,
+      metadata,
+      ata(&treasurer),
+      proposal,
+      dao,
+      receipt,
+      token_program,
+      associated_token::ID,
+      anchor_lang::system_program::ID,
+      anchor_lang::solana_program::sysvar::rent::ID,
+    ]
+  );
+}
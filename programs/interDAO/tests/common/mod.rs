@@ -0,0 +1,800 @@
+//! An in-process bank running the program natively, in the style of solana-program-test.
+//!
+//! Accounts are serialized into the loader input format for every invocation, so reallocs,
+//! owner changes and cross-program invocations behave as they do on-chain. The account rules
+//! of the runtime (ownership, read-only accounts, balanced lamports, rent exemption, CPI
+//! privileges and reentrancy) are verified after every invocation.
+//! The clock is held by the bank and can be warped to test time boundaries.
+//!
+//! solana-program-test can't be used here: its releases matching the Solana version pinned by
+//! anchor-lang 0.28 (< 1.17) conflict with the dependency graph of this workspace. Compute units
+//! are not metered, so the logs carry no `consumed` lines; otherwise they follow the format of the
+//! validator, failures included.
+#![allow(dead_code)]
+
+use anchor_lang::{
+  prelude::*,
+  solana_program::{
+    decode_error::DecodeError,
+    entrypoint::{deserialize, ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS},
+    instruction::{Instruction, InstructionError},
+    program_error::PrintProgramError,
+    program_pack::Pack,
+    program_stubs::{self, SyscallStubs},
+    system_instruction::SystemInstruction,
+    system_program, sysvar,
+  },
+  AccountDeserialize,
+};
+use anchor_spl::{associated_token, token::spl_token, token_2022::spl_token_2022};
+use num_traits::FromPrimitive;
+use spl_token_2022::{
+  extension::{
+    transfer_fee::{TransferFee, TransferFeeConfig},
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions, StateWithExtensionsMut,
+  },
+  state::{Account as TokenAccount, AccountState, Mint},
+};
+use std::{cell::RefCell, collections::HashMap, sync::Once};
+
+pub const GENESIS_TIMESTAMP: i64 = 1_700_000_000;
+pub const SOL: u64 = 1_000_000_000;
+
+const NON_DUP_MARKER: u8 = u8::MAX;
+// The transaction-level instruction plus 4 nested invocations
+const MAX_INVOKE_DEPTH: usize = 5;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BankAccount {
+  pub lamports: u64,
+  pub data: Vec<u8>,
+  pub owner: Pubkey,
+  pub executable: bool,
+}
+
+thread_local! {
+  static CLOCK: RefCell<Clock> = RefCell::new(Clock::default());
+  static INVOKE_STACK: RefCell<Vec<Pubkey>> = const { RefCell::new(Vec::new()) };
+  static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+  static LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+  // The states each running program is verified against, refreshed after its invocations
+  static PRE_ACCOUNTS: RefCell<Vec<HashMap<Pubkey, BankAccount>>> = const { RefCell::new(Vec::new()) };
+  // The runtime failure being reported, if the instruction broke an account rule
+  static VIOLATION: RefCell<Option<&'static str>> = const { RefCell::new(None) };
+  // The error of a failed cross-program invocation, which aborts the whole instruction
+  static ABORTED: RefCell<Option<ProgramError>> = const { RefCell::new(None) };
+}
+
+///
+/// Syscalls are routed to the thread-local state of the bank running on the current thread
+///
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+  fn sol_log(&self, message: &str) {
+    log(format!("Program log: {}", message));
+  }
+  fn sol_log_data(&self, fields: &[&[u8]]) {
+    let fields: Vec<String> = fields.iter().map(base64::encode).collect();
+    log(format!("Program data: {}", fields.join(" ")));
+  }
+  fn sol_invoke_signed(
+    &self,
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+  ) -> ProgramResult {
+    invoke_signed(instruction, account_infos, signers_seeds).inspect_err(|err| {
+      // The runtime doesn't hand the error back to the caller, so nothing runs after it
+      ABORTED.with(|aborted| *aborted.borrow_mut() = Some(err.clone()));
+    })
+  }
+  fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+    let clock = CLOCK.with(|clock| clock.borrow().clone());
+    unsafe { *(var_addr as *mut Clock) = clock };
+    SUCCESS
+  }
+  fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+    unsafe { *(var_addr as *mut Rent) = Rent::default() };
+    SUCCESS
+  }
+  fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+    RETURN_DATA.with(|return_data| return_data.borrow().clone())
+  }
+  fn sol_set_return_data(&self, data: &[u8]) {
+    let program_id = current_program();
+    RETURN_DATA.with(|return_data| {
+      *return_data.borrow_mut() = if data.is_empty() {
+        None
+      } else {
+        Some((program_id, data.to_vec()))
+      }
+    });
+  }
+  fn sol_get_stack_height(&self) -> u64 {
+    INVOKE_STACK.with(|stack| stack.borrow().len() as u64)
+  }
+}
+
+///
+/// Logs follow the runtime format so they can be parsed like those of a real transaction
+///
+fn log(message: String) {
+  if ABORTED.with(|aborted| aborted.borrow().is_none()) {
+    LOGS.with(|logs| logs.borrow_mut().push(message));
+  }
+}
+
+fn current_program() -> Pubkey {
+  INVOKE_STACK.with(|stack| *stack.borrow().last().expect("No program is running"))
+}
+
+///
+/// Fail the instruction the way the runtime reports it
+///
+fn violation(message: &'static str, error: ProgramError) -> ProgramError {
+  VIOLATION.with(|violation| *violation.borrow_mut() = Some(message));
+  error
+}
+
+///
+/// Builtin programs don't go through the loader, so they don't log their return data
+///
+fn is_builtin(program_id: &Pubkey) -> bool {
+  *program_id == system_program::ID
+}
+
+fn snapshot(info: &AccountInfo) -> std::result::Result<BankAccount, ProgramError> {
+  Ok(BankAccount {
+    lamports: info.lamports(),
+    data: info.try_borrow_data()?.to_vec(),
+    owner: *info.owner,
+    executable: info.executable,
+  })
+}
+
+///
+/// The account rules verified by the runtime on the accounts of an instruction
+///
+fn verify(
+  program_id: &Pubkey,
+  pre: &BankAccount,
+  post: &BankAccount,
+  is_writable: bool,
+) -> ProgramResult {
+  let owned = pre.owner == *program_id;
+  if pre.owner != post.owner
+    && (!is_writable || pre.executable || !owned || post.data.iter().any(|byte| *byte != 0))
+  {
+    return Err(violation(
+      "instruction illegally modified the program id of an account",
+      ProgramError::IllegalOwner,
+    ));
+  }
+  if pre.lamports != post.lamports {
+    if !is_writable {
+      return Err(violation(
+        "instruction changed the balance of a read-only account",
+        ProgramError::InvalidAccountData,
+      ));
+    }
+    if post.lamports < pre.lamports && !owned {
+      return Err(violation(
+        "instruction spent from the balance of an account it does not own",
+        ProgramError::InvalidAccountData,
+      ));
+    }
+  }
+  if pre.data != post.data {
+    if !is_writable {
+      return Err(violation(
+        "instruction modified data of a read-only account",
+        ProgramError::InvalidAccountData,
+      ));
+    }
+    if !owned {
+      return Err(violation(
+        "instruction modified data of an account it does not own",
+        ProgramError::InvalidAccountData,
+      ));
+    }
+  }
+  if pre.executable != post.executable {
+    return Err(violation(
+      "instruction changed executable bit of an account",
+      ProgramError::InvalidAccountData,
+    ));
+  }
+  Ok(())
+}
+
+///
+/// Verify the accounts of the running program and the balance of their lamports
+///
+fn verify_all(program_id: &Pubkey, infos: &[AccountInfo]) -> ProgramResult {
+  PRE_ACCOUNTS.with(|pre_accounts| {
+    let pre_accounts = pre_accounts.borrow();
+    let pre_accounts = pre_accounts.last().expect("No program is running");
+    let (mut pre_lamports, mut post_lamports) = (0u128, 0u128);
+    for (key, pre) in pre_accounts.iter() {
+      let info = infos
+        .iter()
+        .find(|info| info.key == key)
+        .expect("Missing account");
+      let post = snapshot(info)?;
+      verify(program_id, pre, &post, info.is_writable)?;
+      pre_lamports += pre.lamports as u128;
+      post_lamports += post.lamports as u128;
+    }
+    if pre_lamports != post_lamports {
+      return Err(violation(
+        "sum of account balances before and after instruction do not match",
+        ProgramError::InvalidAccountData,
+      ));
+    }
+    Ok(())
+  })
+}
+
+///
+/// Programs available in the bank
+///
+fn dispatch<'a>(
+  program_id: &Pubkey,
+  accounts: &'a [AccountInfo<'a>],
+  data: &[u8],
+) -> ProgramResult {
+  if *program_id == inter_dao::ID {
+    inter_dao::entry(program_id, accounts, data)
+  } else if *program_id == system_program::ID {
+    process_system_instruction(accounts, data)
+  } else if *program_id == spl_token::ID {
+    spl_token::processor::Processor::process(program_id, accounts, data)
+      .map_err(print::<spl_token::error::TokenError>)
+  } else if *program_id == spl_token_2022::ID {
+    spl_token_2022::processor::Processor::process(program_id, accounts, data)
+      .map_err(print::<spl_token_2022::error::TokenError>)
+  } else if *program_id == associated_token::ID {
+    spl_associated_token_account::processor::process_instruction(program_id, accounts, data)
+  } else {
+    Err(ProgramError::IncorrectProgramId)
+  }
+}
+
+///
+/// The token programs log their errors from their entrypoints
+///
+fn print<E>(err: ProgramError) -> ProgramError
+where
+  E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+{
+  err.print::<E>();
+  err
+}
+
+///
+/// The subset of the system program used by the tests
+///
+fn process_system_instruction(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+  let instruction: SystemInstruction =
+    bincode::deserialize(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+  let debit = |from: &AccountInfo, to: &AccountInfo, lamports: u64| -> ProgramResult {
+    if !from.is_signer {
+      return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *from.owner != system_program::ID || !from.data_is_empty() {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    let balance = from
+      .lamports()
+      .checked_sub(lamports)
+      .ok_or(ProgramError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = balance;
+    **to.try_borrow_mut_lamports()? = to
+      .lamports()
+      .checked_add(lamports)
+      .ok_or(ProgramError::InvalidArgument)?;
+    Ok(())
+  };
+  let allocate = |account: &AccountInfo, space: u64| -> ProgramResult {
+    if !account.is_signer {
+      return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *account.owner != system_program::ID || !account.data_is_empty() {
+      return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    account.realloc(space as usize, true)
+  };
+  let assign = |account: &AccountInfo, owner: &Pubkey| -> ProgramResult {
+    if !account.is_signer {
+      return Err(ProgramError::MissingRequiredSignature);
+    }
+    account.assign(owner);
+    Ok(())
+  };
+  match instruction {
+    SystemInstruction::CreateAccount {
+      lamports,
+      space,
+      owner,
+    } => {
+      let (from, to) = (&accounts[0], &accounts[1]);
+      if to.lamports() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+      }
+      debit(from, to, lamports)?;
+      allocate(to, space)?;
+      assign(to, &owner)
+    }
+    SystemInstruction::Transfer { lamports } => debit(&accounts[0], &accounts[1], lamports),
+    SystemInstruction::Allocate { space } => allocate(&accounts[0], space),
+    SystemInstruction::Assign { owner } => assign(&accounts[0], &owner),
+    _ => Err(ProgramError::InvalidInstructionData),
+  }
+}
+
+///
+/// Serialize the accounts in the loader input format, run the program and write back the writable accounts
+///
+fn execute(
+  program_id: &Pubkey,
+  metas: &[AccountMeta],
+  accounts: &mut HashMap<Pubkey, BankAccount>,
+  data: &[u8],
+) -> ProgramResult {
+  let mut input: Vec<u8> = Vec::new();
+  input.extend_from_slice(&(metas.len() as u64).to_le_bytes());
+  for (i, meta) in metas.iter().enumerate() {
+    let first = metas
+      .iter()
+      .position(|other| other.pubkey == meta.pubkey)
+      .unwrap_or(i);
+    if first < i {
+      input.push(first as u8);
+      input.extend_from_slice(&[0; 7]);
+      continue;
+    }
+    // Duplicated accounts share the highest privileges
+    let (is_signer, is_writable) = metas
+      .iter()
+      .filter(|other| other.pubkey == meta.pubkey)
+      .fold((false, false), |(is_signer, is_writable), other| {
+        (
+          is_signer || other.is_signer,
+          is_writable || other.is_writable,
+        )
+      });
+    let account = accounts.get(&meta.pubkey).cloned().unwrap_or_default();
+    input.push(NON_DUP_MARKER);
+    input.push(is_signer as u8);
+    input.push(is_writable as u8);
+    input.push(account.executable as u8);
+    input.extend_from_slice(&[0; 4]);
+    input.extend_from_slice(meta.pubkey.as_ref());
+    input.extend_from_slice(account.owner.as_ref());
+    input.extend_from_slice(&account.lamports.to_le_bytes());
+    input.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+    input.extend_from_slice(&account.data);
+    input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+    input.resize(input.len().div_ceil(8) * 8, 0);
+    input.extend_from_slice(&0u64.to_le_bytes());
+  }
+  input.extend_from_slice(&(data.len() as u64).to_le_bytes());
+  input.extend_from_slice(data);
+  input.extend_from_slice(program_id.as_ref());
+  // Keep the input aligned as the loader does
+  let mut buffer = vec![0u64; input.len().div_ceil(8)];
+  let buffer_ptr = buffer.as_mut_ptr() as *mut u8;
+  unsafe { std::ptr::copy_nonoverlapping(input.as_ptr(), buffer_ptr, input.len()) };
+  let (program_id, infos, data) = unsafe { deserialize(buffer_ptr) };
+
+  let depth = INVOKE_STACK.with(|stack| {
+    stack.borrow_mut().push(*program_id);
+    stack.borrow().len()
+  });
+  PRE_ACCOUNTS.with(|pre_accounts| {
+    let pre_accounts_of_program = infos
+      .iter()
+      .map(|info| {
+        (
+          *info.key,
+          accounts.get(info.key).cloned().unwrap_or_default(),
+        )
+      })
+      .collect();
+    pre_accounts.borrow_mut().push(pre_accounts_of_program)
+  });
+  log(format!("Program {} invoke [{}]", program_id, depth));
+  let result = dispatch(program_id, &infos, data).and_then(|()| verify_all(program_id, &infos));
+  let result = match ABORTED.with(|aborted| aborted.borrow().clone()) {
+    Some(err) => Err(err),
+    None => result,
+  };
+  PRE_ACCOUNTS.with(|pre_accounts| pre_accounts.borrow_mut().pop());
+  INVOKE_STACK.with(|stack| stack.borrow_mut().pop());
+  match &result {
+    Ok(()) => {
+      if let Some((program_id, data)) = RETURN_DATA.with(|return_data| return_data.borrow().clone())
+      {
+        if !is_builtin(&program_id) {
+          log(format!(
+            "Program return: {} {}",
+            program_id,
+            base64::encode(data)
+          ));
+        }
+      }
+      log(format!("Program {} success", program_id));
+    }
+    Err(err) => {
+      let message = VIOLATION.with(|violation| *violation.borrow()).map_or_else(
+        || InstructionError::from(u64::from(err.clone())).to_string(),
+        str::to_string,
+      );
+      // Every program of the aborted invocation stack reports the failure
+      LOGS.with(|logs| {
+        logs
+          .borrow_mut()
+          .push(format!("Program {} failed: {}", program_id, message))
+      });
+    }
+  }
+  result?;
+
+  for info in infos.iter().filter(|info| info.is_writable) {
+    accounts.insert(*info.key, snapshot(info)?);
+  }
+  Ok(())
+}
+
+///
+/// Cross-program invocations run on copies of the caller accounts, which are written back on success.
+/// As in the runtime, the changes of the caller are verified before the call
+///
+fn invoke_signed(
+  instruction: &Instruction,
+  account_infos: &[AccountInfo],
+  signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+  let caller = current_program();
+  let (depth, is_reentrant) = INVOKE_STACK.with(|stack| {
+    let stack = stack.borrow();
+    (
+      stack.len(),
+      stack.contains(&instruction.program_id) && instruction.program_id != caller,
+    )
+  });
+  if depth >= MAX_INVOKE_DEPTH {
+    return Err(violation(
+      "Cross-program invocation call depth too deep",
+      ProgramError::InvalidArgument,
+    ));
+  }
+  if is_reentrant {
+    return Err(violation(
+      "Cross-program invocation reentrancy not allowed for this instruction",
+      ProgramError::InvalidArgument,
+    ));
+  }
+  let signers = signers_seeds
+    .iter()
+    .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+    .collect::<std::result::Result<Vec<Pubkey>, _>>()
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+  let find = |pubkey: &Pubkey| {
+    account_infos
+      .iter()
+      .find(|info| info.key == pubkey)
+      .ok_or(ProgramError::NotEnoughAccountKeys)
+  };
+
+  let mut accounts = HashMap::new();
+  for meta in instruction.accounts.iter() {
+    let info = find(&meta.pubkey)?;
+    if meta.is_signer && !info.is_signer && !signers.contains(&meta.pubkey) {
+      return Err(violation(
+        "Cross-program invocation with unauthorized signer or writable account",
+        ProgramError::MissingRequiredSignature,
+      ));
+    }
+    if meta.is_writable && !info.is_writable {
+      return Err(violation(
+        "Cross-program invocation with unauthorized signer or writable account",
+        ProgramError::InvalidArgument,
+      ));
+    }
+    let account = snapshot(info)?;
+    PRE_ACCOUNTS.with(|pre_accounts| {
+      let pre_accounts = pre_accounts.borrow();
+      let pre = &pre_accounts.last().expect("No program is running")[&meta.pubkey];
+      verify(&caller, pre, &account, info.is_writable)
+    })?;
+    accounts.insert(meta.pubkey, account);
+  }
+
+  RETURN_DATA.with(|return_data| *return_data.borrow_mut() = None);
+  execute(
+    &instruction.program_id,
+    &instruction.accounts,
+    &mut accounts,
+    &instruction.data,
+  )?;
+
+  for meta in instruction.accounts.iter() {
+    let info = find(&meta.pubkey)?;
+    if meta.is_writable {
+      let account = &accounts[&meta.pubkey];
+      **info.try_borrow_mut_lamports()? = account.lamports;
+      if *info.owner != account.owner {
+        info.assign(&account.owner);
+      }
+      info.realloc(account.data.len(), false)?;
+      info.try_borrow_mut_data()?.copy_from_slice(&account.data);
+    }
+    // The changes of the callee are not attributed to the caller
+    let account = snapshot(info)?;
+    PRE_ACCOUNTS.with(|pre_accounts| {
+      let mut pre_accounts = pre_accounts.borrow_mut();
+      pre_accounts
+        .last_mut()
+        .expect("No program is running")
+        .insert(meta.pubkey, account)
+    });
+  }
+  Ok(())
+}
+
+pub struct Bank {
+  accounts: HashMap<Pubkey, BankAccount>,
+}
+
+impl Bank {
+  pub fn new() -> Self {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+      program_stubs::set_syscall_stubs(Box::new(Stubs));
+    });
+    CLOCK.with(|clock| {
+      *clock.borrow_mut() = Clock {
+        slot: 1,
+        unix_timestamp: GENESIS_TIMESTAMP,
+        ..Clock::default()
+      }
+    });
+
+    let mut bank = Bank {
+      accounts: HashMap::new(),
+    };
+    for program_id in [
+      inter_dao::ID,
+      system_program::ID,
+      spl_token::ID,
+      spl_token_2022::ID,
+      associated_token::ID,
+    ] {
+      bank.set_account(
+        program_id,
+        BankAccount {
+          lamports: 1,
+          data: vec![],
+          owner: anchor_lang::solana_program::bpf_loader::ID,
+          executable: true,
+        },
+      );
+    }
+    let rent = Rent::default();
+    bank.set_account(
+      sysvar::rent::ID,
+      BankAccount {
+        lamports: 1,
+        data: bincode::serialize(&rent).unwrap(),
+        owner: sysvar::ID,
+        executable: false,
+      },
+    );
+    bank
+  }
+
+  pub fn now(&self) -> i64 {
+    CLOCK.with(|clock| clock.borrow().unix_timestamp)
+  }
+
+  pub fn warp_to(&mut self, unix_timestamp: i64) {
+    CLOCK.with(|clock| {
+      let mut clock = clock.borrow_mut();
+      clock.slot += 1;
+      clock.unix_timestamp = unix_timestamp;
+    });
+  }
+
+  pub fn warp(&mut self, seconds: i64) {
+    self.warp_to(self.now() + seconds);
+  }
+
+  ///
+  /// The logs of the last transaction
+  ///
+  pub fn logs(&self) -> Vec<String> {
+    LOGS.with(|logs| logs.borrow().clone())
+  }
+
+  pub fn account(&self, pubkey: &Pubkey) -> Option<&BankAccount> {
+    self.accounts.get(pubkey)
+  }
+
+  pub fn set_account(&mut self, pubkey: Pubkey, account: BankAccount) {
+    self.accounts.insert(pubkey, account);
+  }
+
+  pub fn lamports(&self, pubkey: &Pubkey) -> u64 {
+    self.account(pubkey).map_or(0, |account| account.lamports)
+  }
+
+  pub fn airdrop(&mut self, pubkey: &Pubkey, lamports: u64) {
+    let account = self.accounts.entry(*pubkey).or_default();
+    account.lamports += lamports;
+  }
+
+  pub fn get<T: AccountDeserialize>(&self, pubkey: &Pubkey) -> T {
+    let account = self.account(pubkey).expect("Account not found");
+    T::try_deserialize(&mut account.data.as_slice()).expect("Invalid account data")
+  }
+
+  ///
+  /// Instructions run atomically. The bank is left untouched on failure
+  ///
+  pub fn process_transaction(&mut self, instructions: &[Instruction]) -> ProgramResult {
+    let mut accounts = self.accounts.clone();
+    LOGS.with(|logs| logs.borrow_mut().clear());
+    for instruction in instructions {
+      RETURN_DATA.with(|return_data| *return_data.borrow_mut() = None);
+      VIOLATION.with(|violation| *violation.borrow_mut() = None);
+      ABORTED.with(|aborted| *aborted.borrow_mut() = None);
+      execute(
+        &instruction.program_id,
+        &instruction.accounts,
+        &mut accounts,
+        &instruction.data,
+      )?;
+    }
+    // Changed accounts must be left rent-exempt, or drained and purged
+    let rent = Rent::default();
+    for (pubkey, account) in accounts.iter() {
+      let is_changed = self.accounts.get(pubkey) != Some(account);
+      if is_changed && account.lamports > 0 && !rent.is_exempt(account.lamports, account.data.len())
+      {
+        return Err(ProgramError::AccountNotRentExempt);
+      }
+    }
+    accounts.retain(|_, account| account.lamports > 0);
+    self.accounts = accounts;
+    Ok(())
+  }
+
+  pub fn process(&mut self, instruction: Instruction) -> ProgramResult {
+    self.process_transaction(&[instruction])
+  }
+
+  pub fn return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+    RETURN_DATA.with(|return_data| return_data.borrow().clone())
+  }
+
+  ///
+  /// Token helpers
+  ///
+  fn set_token_state(&mut self, pubkey: Pubkey, data: Vec<u8>, token_program: Pubkey) {
+    self.set_account(
+      pubkey,
+      BankAccount {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: token_program,
+        executable: false,
+      },
+    );
+  }
+
+  pub fn create_mint(&mut self, mint_authority: &Pubkey, supply: u64, decimals: u8) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint::pack(
+      spl_token::state::Mint {
+        mint_authority: Some(*mint_authority).into(),
+        supply,
+        decimals,
+        is_initialized: true,
+        freeze_authority: None.into(),
+      },
+      &mut data,
+    )
+    .unwrap();
+    self.set_token_state(mint, data, spl_token::ID);
+    mint
+  }
+
+  ///
+  /// A Token-2022 mint withholding a transfer fee in basis points, capped at maximum_fee
+  ///
+  pub fn create_mint_2022(
+    &mut self,
+    mint_authority: &Pubkey,
+    supply: u64,
+    decimals: u8,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+  ) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let mut data =
+      vec![0; ExtensionType::get_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    let transfer_fee = TransferFee {
+      epoch: 0.into(),
+      maximum_fee: maximum_fee.into(),
+      transfer_fee_basis_points: transfer_fee_basis_points.into(),
+    };
+    let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+    config.older_transfer_fee = transfer_fee;
+    config.newer_transfer_fee = transfer_fee;
+    state.base = Mint {
+      mint_authority: Some(*mint_authority).into(),
+      supply,
+      decimals,
+      is_initialized: true,
+      freeze_authority: None.into(),
+    };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    self.set_token_state(mint, data, spl_token_2022::ID);
+    mint
+  }
+
+  ///
+  /// The associated token account of the owner, carrying the extensions required by the mint
+  ///
+  pub fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+    let mint_account = self.account(mint).expect("Mint not found").clone();
+    let token_account = associated_token::get_associated_token_address_with_program_id(
+      owner,
+      mint,
+      &mint_account.owner,
+    );
+    let base = TokenAccount {
+      mint: *mint,
+      owner: *owner,
+      amount,
+      state: AccountState::Initialized,
+      ..TokenAccount::default()
+    };
+    let data = if mint_account.owner == spl_token::ID {
+      let mut data = vec![0; TokenAccount::LEN];
+      TokenAccount::pack(base, &mut data).unwrap();
+      data
+    } else {
+      let mint_extensions = StateWithExtensions::<Mint>::unpack(&mint_account.data)
+        .unwrap()
+        .get_extension_types()
+        .unwrap();
+      let extensions = ExtensionType::get_required_init_account_extensions(&mint_extensions);
+      let mut data = vec![0; ExtensionType::get_account_len::<TokenAccount>(&extensions)];
+      let mut state =
+        StateWithExtensionsMut::<TokenAccount>::unpack_uninitialized(&mut data).unwrap();
+      for extension in extensions {
+        state.init_account_extension_from_type(extension).unwrap();
+      }
+      state.base = base;
+      state.pack_base();
+      state.init_account_type().unwrap();
+      data
+    };
+    self.set_token_state(token_account, data, mint_account.owner);
+    token_account
+  }
+
+  pub fn token_balance(&self, token_account: &Pubkey) -> u64 {
+    let account = self.account(token_account).expect("Account not found");
+    StateWithExtensions::<TokenAccount>::unpack(&account.data)
+      .expect("Invalid token account")
+      .base
+      .amount
+  }
+}
@@ -0,0 +1,235 @@
+use inter_dao::{
+  client::events::{parse_logs, InterDaoEvent},
+  HashAlgorithm, InitializeProposalEvent, ProposalInstructionKind, ProposalState, ReceiptAction,
+  VoteForEvent,
+};
+
+// Logs recorded by the bank of the governance tests (see `record_event_fixtures`), not by a
+// validator: an autonomous DAO with a supply of 1000 and a proposal transferring 0.5 SOL out
+// of the master, passed by a 600 vote. The bank doesn't meter compute units, so the
+// `consumed` lines a validator logs are added by the tests that need them
+const INITIALIZE_PROPOSAL: &str = include_str!("fixtures/initialize_proposal.log");
+const VOTE_FOR: &str = include_str!("fixtures/vote_for.log");
+const GET_PROPOSAL_STATUS: &str = include_str!("fixtures/get_proposal_status.log");
+const EXECUTE_PROPOSAL_INSTRUCTION: &str =
+  include_str!("fixtures/execute_proposal_instruction.log");
+const CLOSE: &str = include_str!("fixtures/close.log");
+const UPDATE_DAO_GUARDIANS: &str = include_str!("fixtures/update_dao_guardians.log");
+const EDIT_CONTENT: &str = include_str!("fixtures/edit_content.log");
+// A second vote, failing in the token program as the voter has nothing left
+const VOTE_FOR_FAILED: &str = include_str!("fixtures/vote_for_failed.log");
+
+fn parse(fixture: &str) -> Vec<InterDaoEvent> {
+  let logs: Vec<&str> = fixture.lines().collect();
+  parse_logs(&logs).unwrap()
+}
+
+#[test]
+fn test_initialize_proposal() {
+  let events = parse(INITIALIZE_PROPOSAL);
+  assert_eq!(events.len(), 2);
+  let proposal = match &events[0] {
+    InterDaoEvent::InitializeProposal(event) => {
+      assert_eq!(event.version, InitializeProposalEvent::VERSION);
+      assert_eq!(event.index, 0);
+      assert_eq!(event.end_date - event.start_date, 100);
+      event.proposal
+    }
+    event => panic!("unexpected {}", event.name()),
+  };
+  match &events[1] {
+    InterDaoEvent::InitializeProposalTemplate(event) => {
+      assert_eq!(event.proposal, proposal);
+      assert!(matches!(
+        event.kind,
+        ProposalInstructionKind::SolTransfer {
+          lamports: 500_000_000,
+          ..
+        }
+      ));
+      assert_eq!(event.accounts.len(), 2);
+      assert!(event.accounts[0].is_master);
+    }
+    event => panic!("unexpected {}", event.name()),
+  }
+}
+
+#[test]
+fn test_vote_for_skips_inner_programs() {
+  // The associated token, system and token programs log in between
+  let events = parse(VOTE_FOR);
+  assert_eq!(events.len(), 1);
+  match &events[0] {
+    InterDaoEvent::VoteFor(event) => {
+      assert_eq!(event.version, VoteForEvent::VERSION);
+      assert_eq!(event.action, ReceiptAction::VoteFor);
+      assert_eq!(event.amount, 600);
+      assert_eq!(event.power, 600);
+    }
+    event => panic!("unexpected {}", event.name()),
+  }
+}
+
+#[test]
+fn test_return_data_is_not_an_event() {
+  assert!(parse(GET_PROPOSAL_STATUS).is_empty());
+}
+
+#[test]
+fn test_execute_proposal_instruction() {
+  let events = parse(EXECUTE_PROPOSAL_INSTRUCTION);
+  assert_eq!(events.len(), 1);
+  match &events[0] {
+    InterDaoEvent::ExecuteProposalInstruction(event) => {
+      assert_eq!(event.index, 0);
+      assert_eq!(event.state, ProposalState::Executed);
+    }
+    event => panic!("unexpected {}", event.name()),
+  }
+}
+
+#[test]
+fn test_close() {
+  let events = parse(CLOSE);
+  assert_eq!(events.len(), 1);
+  match &events[0] {
+    InterDaoEvent::Close(event) => {
+      assert_eq!(event.action, ReceiptAction::VoteFor);
+      assert_eq!(event.amount, 600);
+    }
+    event => panic!("unexpected {}", event.name()),
+  }
+}
+
+#[test]
+fn test_update_dao_guardians() {
+  let events = parse(UPDATE_DAO_GUARDIANS);
+  assert_eq!(events.len(), 1);
+  match &events[0] {
+    InterDaoEvent::UpdateDaoGuardians(event) => {
+      assert_eq!(event.old_timelock, 0);
+      assert_eq!(event.new_timelock, 0);
+      assert!(event.new_guardians.is_empty());
+    }
+    event => panic!("unexpected {}", event.name()),
+  }
+}
+
+#[test]
+fn test_edit_content() {
+  let events = parse(EDIT_CONTENT);
+  assert_eq!(events.len(), 1);
+  match &events[0] {
+    InterDaoEvent::Content(event) => {
+      assert_eq!(event.revision, 1);
+      assert_eq!(event.old_metadata, [1; 32]);
+      assert_eq!(event.new_metadata, [2; 32]);
+      assert_eq!(event.old_hash_algorithm, HashAlgorithm::Sha256);
+      assert_eq!(event.new_hash_algorithm, HashAlgorithm::Blake3);
+      assert!(event.old_metadata_uri.is_empty());
+      assert_eq!(event.new_metadata_uri, "ipfs://edit");
+    }
+    event => panic!("unexpected {}", event.name()),
+  }
+}
+
+#[test]
+fn test_whole_flow_in_order() {
+  let logs: Vec<&str> = [
+    INITIALIZE_PROPOSAL,
+    VOTE_FOR,
+    GET_PROPOSAL_STATUS,
+    EXECUTE_PROPOSAL_INSTRUCTION,
+    CLOSE,
+  ]
+  .iter()
+  .flat_map(|fixture| fixture.lines())
+  .collect();
+  let names: Vec<&str> = parse_logs(&logs)
+    .unwrap()
+    .iter()
+    .map(InterDaoEvent::name)
+    .collect();
+  assert_eq!(
+    names,
+    [
+      "InitializeProposalEvent",
+      "InitializeProposalTemplateEvent",
+      "VoteForEvent",
+      "ExecuteProposalInstructionEvent",
+      "CloseEvent",
+    ]
+  );
+}
+
+#[test]
+fn test_events_of_other_programs_are_skipped() {
+  let data = VOTE_FOR
+    .lines()
+    .find(|log| log.starts_with("Program data: "))
+    .unwrap();
+  let logs = [
+    "Program 11111111111111111111111111111111 invoke [1]",
+    data,
+    "Program 11111111111111111111111111111111 success",
+  ];
+  assert!(parse_logs(&logs).unwrap().is_empty());
+}
+
+#[test]
+fn test_consumed_compute_units_are_skipped() {
+  // The validator meters every program but the builtins, logging before the result
+  let logs: Vec<String> = VOTE_FOR
+    .lines()
+    .flat_map(|log| {
+      let mut lines = vec![];
+      if let Some(program) = log
+        .strip_prefix("Program ")
+        .and_then(|log| log.strip_suffix(" success"))
+      {
+        if program != "11111111111111111111111111111111" {
+          lines.push(format!(
+            "Program {} consumed 4242 of 200000 compute units",
+            program
+          ));
+        }
+      }
+      lines.push(log.to_string());
+      lines
+    })
+    .collect();
+  assert!(logs.len() > VOTE_FOR.lines().count());
+  let events = parse_logs(&logs).unwrap();
+  assert_eq!(events.len(), 1);
+  assert_eq!(events[0].name(), "VoteForEvent");
+}
+
+#[test]
+fn test_failed_transaction() {
+  assert!(VOTE_FOR_FAILED
+    .lines()
+    .any(|log| log.ends_with("failed: custom program error: 0x1")));
+  assert!(parse(VOTE_FOR_FAILED).is_empty());
+}
+
+#[test]
+fn test_events_of_a_failed_instruction_are_dropped() {
+  let program = "Program xWfewaindgnhppdM9qpAhoDpWrdm1myRmgsPLPhGEuP";
+  let success = format!("{} success", program);
+  let failed = format!("{} failed: custom program error: 0x1", program);
+  let logs: Vec<&str> = VOTE_FOR
+    .lines()
+    .map(|log| if log == success { failed.as_str() } else { log })
+    .collect();
+  assert!(parse_logs(&logs).unwrap().is_empty());
+}
+
+#[test]
+fn test_malformed_event() {
+  let logs = [
+    "Program xWfewaindgnhppdM9qpAhoDpWrdm1myRmgsPLPhGEuP invoke [1]",
+    "Program data: not-base64!",
+    "Program xWfewaindgnhppdM9qpAhoDpWrdm1myRmgsPLPhGEuP success",
+  ];
+  assert!(parse_logs(&logs).is_err());
+}
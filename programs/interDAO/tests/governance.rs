@@ -0,0 +1,2209 @@
+mod common;
+
+use anchor_lang::{
+  prelude::*,
+  solana_program::{
+    entrypoint::ProgramResult, instruction::Instruction, system_instruction, system_program, sysvar,
+  },
+  InstructionData,
+};
+use anchor_spl::associated_token;
+use common::{Bank, SOL};
+use inter_dao::{
+  ConsensusMechanism, ConsensusQuorum, Content, Dao, DaoRegime, ErrorCode, Escrow, HashAlgorithm,
+  Proposal, ProposalInstruction, ProposalInstructionKind, ProposalState, ProposalStatus, Receipt,
+  ReceiptAction, VoterRecord,
+};
+
+const SUPPLY: u64 = 1_000;
+const NOTICE: i64 = 10;
+const VOTING_DURATION: i64 = 100;
+const EXECUTION_WINDOW: i64 = 3_600;
+const RETRY_WINDOW: i64 = 600;
+const TRANSFERRED: u64 = SOL / 2;
+
+fn error(code: ErrorCode) -> ProgramResult {
+  Err(ProgramError::Custom(code.into()))
+}
+
+fn anchor_error(code: anchor_lang::error::ErrorCode) -> ProgramResult {
+  Err(ProgramError::Custom(code.into()))
+}
+
+///
+/// An autonomous public DAO governed by the mint
+///
+fn initialize_dao(bank: &mut Bank, authority: &Pubkey, mint: &Pubkey, is_nft: bool) -> Pubkey {
+  let dao = Pubkey::new_unique();
+  let (master, _) = Pubkey::find_program_address(&[b"master", dao.as_ref()], &inter_dao::ID);
+  bank
+    .process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::InitializeDAO {
+        authority: *authority,
+        dao,
+        master,
+        mint: *mint,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::InitializeDao {
+        regime: DaoRegime::Autonomous,
+        supply: SUPPLY,
+        metadata: [0; 32],
+        hash_algorithm: HashAlgorithm::Sha256,
+        metadata_uri: String::new(),
+        is_nft,
+        is_public: true,
+        min_voting_duration: 60,
+        max_voting_duration: 86_400,
+        min_notice_period: NOTICE,
+        execution_window: EXECUTION_WINDOW,
+        retry_window: RETRY_WINDOW,
+      }
+      .data(),
+    })
+    .unwrap();
+  dao
+}
+
+///
+/// An autonomous DAO with a single proposal transferring SOL out of the master
+///
+struct Fixture {
+  bank: Bank,
+  authority: Pubkey,
+  voter: Pubkey,
+  recipient: Pubkey,
+  mint: Pubkey,
+  token_program: Pubkey,
+  dao: Pubkey,
+  master: Pubkey,
+  proposal: Pubkey,
+  proposal_instruction: Pubkey,
+  start_date: i64,
+  end_date: i64,
+  independent_instructions: bool,
+}
+
+impl Fixture {
+  fn new() -> Self {
+    Self::with_mint(|bank, authority| bank.create_mint(authority, SUPPLY, 0))
+  }
+
+  ///
+  /// The governance mint is a Token-2022 mint withholding a transfer fee
+  ///
+  fn with_transfer_fee(transfer_fee_basis_points: u16) -> Self {
+    Self::with_mint(|bank, authority| {
+      bank.create_mint_2022(authority, SUPPLY, 0, transfer_fee_basis_points, u64::MAX)
+    })
+  }
+
+  fn with_mint(create_mint: impl FnOnce(&mut Bank, &Pubkey) -> Pubkey) -> Self {
+    let mut bank = Bank::new();
+    let authority = Pubkey::new_unique();
+    let voter = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    bank.airdrop(&authority, 10 * SOL);
+    bank.airdrop(&voter, 10 * SOL);
+    let mint = create_mint(&mut bank, &authority);
+    let token_program = bank.account(&mint).unwrap().owner;
+    bank.create_token_account(&mint, &voter, SUPPLY * 3 / 5);
+
+    let dao = initialize_dao(&mut bank, &authority, &mint, false);
+    let (master, _) = Pubkey::find_program_address(&[b"master", dao.as_ref()], &inter_dao::ID);
+    bank.airdrop(&master, 10 * SOL);
+
+    let mut fixture = Fixture {
+      bank,
+      authority,
+      voter,
+      recipient,
+      mint,
+      token_program,
+      dao,
+      master,
+      proposal: Pubkey::default(),
+      proposal_instruction: Pubkey::default(),
+      start_date: 0,
+      end_date: 0,
+      independent_instructions: false,
+    };
+    fixture.initialize_proposal(0);
+    fixture
+  }
+
+  fn initialize_proposal(&mut self, nonce: u64) {
+    self.start_date = self.bank.now() + NOTICE;
+    self.end_date = self.start_date + VOTING_DURATION;
+    let (proposal, _) = Pubkey::find_program_address(
+      &[b"proposal", &nonce.to_le_bytes(), self.dao.as_ref()],
+      &inter_dao::ID,
+    );
+    self.proposal = proposal;
+    self.proposal_instruction = Pubkey::new_unique();
+    self
+      .bank
+      .process_transaction(&[
+        Instruction {
+          program_id: inter_dao::ID,
+          accounts: inter_dao::accounts::InitializeProposal {
+            caller: self.authority,
+            proposal,
+            dao: self.dao,
+            taxman: self.authority,
+            revenueman: self.authority,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+          }
+          .to_account_metas(None),
+          data: inter_dao::instruction::InitializeProposal {
+            consensus_mechanism: ConsensusMechanism::StakedTokenCounter,
+            consesus_quorum: ConsensusQuorum::Half,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            metadata: [0; 32],
+            hash_algorithm: HashAlgorithm::Sha256,
+            metadata_uri: String::new(),
+            independent_instructions: self.independent_instructions,
+            tax: 0,
+            revenue: 0,
+          }
+          .data(),
+        },
+        Instruction {
+          program_id: inter_dao::ID,
+          accounts: inter_dao::accounts::InitializeProposalTemplate {
+            caller: self.authority,
+            proposal_instruction: self.proposal_instruction,
+            proposal,
+            dao: self.dao,
+            master: self.master,
+            mint: None,
+            token_program: None,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+          }
+          .to_account_metas(None),
+          data: inter_dao::instruction::InitializeProposalTemplate {
+            tx_idx: 0,
+            kind: ProposalInstructionKind::SolTransfer {
+              dst: self.recipient,
+              lamports: TRANSFERRED,
+            },
+          }
+          .data(),
+        },
+      ])
+      .unwrap();
+  }
+
+  fn receipt(&self, index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+      &[
+        b"receipt",
+        &index.to_le_bytes(),
+        self.proposal.as_ref(),
+        self.voter.as_ref(),
+      ],
+      &inter_dao::ID,
+    )
+    .0
+  }
+
+  fn voter_record(&self) -> Pubkey {
+    Pubkey::find_program_address(
+      &[b"voter_record", self.proposal.as_ref(), self.voter.as_ref()],
+      &inter_dao::ID,
+    )
+    .0
+  }
+
+  fn receipts(&self) -> u64 {
+    match self.bank.account(&self.voter_record()) {
+      Some(_) => self.bank.get::<VoterRecord>(&self.voter_record()).receipts,
+      None => 0,
+    }
+  }
+
+  fn treasurer(&self) -> Pubkey {
+    Pubkey::find_program_address(&[b"treasurer", self.proposal.as_ref()], &inter_dao::ID).0
+  }
+
+  fn voter_token_account(&self) -> Pubkey {
+    self.token_account(&self.voter)
+  }
+
+  fn token_account(&self, owner: &Pubkey) -> Pubkey {
+    associated_token::get_associated_token_address_with_program_id(
+      owner,
+      &self.mint,
+      &self.token_program,
+    )
+  }
+
+  fn vote_for(&mut self, amount: u64) -> ProgramResult {
+    let treasurer = self.treasurer();
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::VoteFor {
+        authority: self.voter,
+        src: self.voter_token_account(),
+        treasurer,
+        mint: self.mint,
+        treasury: self.token_account(&treasurer),
+        proposal: self.proposal,
+        dao: self.dao,
+        receipt: self.receipt(self.receipts()),
+        voter_record: self.voter_record(),
+        taxman: self.authority,
+        revenueman: self.authority,
+        token_program: self.token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::VoteFor {
+        amount,
+        tax: 0,
+        revenue: 0,
+      }
+      .data(),
+    })
+  }
+
+  fn vote_against(&mut self, amount: u64) -> ProgramResult {
+    let treasurer = self.treasurer();
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::VoteAgainst {
+        authority: self.voter,
+        src: self.voter_token_account(),
+        treasurer,
+        mint: self.mint,
+        treasury: self.token_account(&treasurer),
+        proposal: self.proposal,
+        dao: self.dao,
+        receipt: self.receipt(self.receipts()),
+        voter_record: self.voter_record(),
+        taxman: self.authority,
+        revenueman: self.authority,
+        token_program: self.token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::VoteAgainst {
+        amount,
+        tax: 0,
+        revenue: 0,
+      }
+      .data(),
+    })
+  }
+
+  fn increase_vote(&mut self, index: u64, amount: u64) -> ProgramResult {
+    let treasurer = self.treasurer();
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::IncreaseVote {
+        authority: self.voter,
+        src: self.voter_token_account(),
+        treasurer,
+        mint: self.mint,
+        treasury: self.token_account(&treasurer),
+        proposal: self.proposal,
+        dao: self.dao,
+        receipt: self.receipt(index),
+        taxman: self.authority,
+        revenueman: self.authority,
+        token_program: self.token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::IncreaseVote {
+        amount,
+        tax: 0,
+        revenue: 0,
+      }
+      .data(),
+    })
+  }
+
+  fn execute(&mut self) -> ProgramResult {
+    let proposal_instruction: ProposalInstruction = self.bank.get(&self.proposal_instruction);
+    let mut accounts = inter_dao::accounts::ExecuteProposalInstruction {
+      caller: self.voter,
+      proposal: self.proposal,
+      proposal_instruction: self.proposal_instruction,
+      dao: self.dao,
+      master: self.master,
+      invoked_program: proposal_instruction.invoked_program,
+    }
+    .to_account_metas(None);
+    // The master signs by seeds
+    accounts.extend(proposal_instruction.accounts.iter().map(|account| {
+      if account.is_writable {
+        AccountMeta::new(account.pubkey, false)
+      } else {
+        AccountMeta::new_readonly(account.pubkey, false)
+      }
+    }));
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts,
+      data: inter_dao::instruction::ExecuteProposalInstruction {}.data(),
+    })
+  }
+
+  ///
+  /// Append a templated instruction to the proposal, which becomes the one to execute
+  ///
+  fn add_template(
+    &mut self,
+    caller: Pubkey,
+    kind: ProposalInstructionKind,
+    mint: Option<Pubkey>,
+  ) -> ProgramResult {
+    self.proposal_instruction = Pubkey::new_unique();
+    let token_program = mint
+      .and_then(|mint| self.bank.account(&mint))
+      .map(|mint| mint.owner);
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::InitializeProposalTemplate {
+        caller,
+        proposal_instruction: self.proposal_instruction,
+        proposal: self.proposal,
+        dao: self.dao,
+        master: self.master,
+        mint,
+        token_program,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::InitializeProposalTemplate { tx_idx: 0, kind }.data(),
+    })
+  }
+
+  ///
+  /// Replace the instruction to execute with a raw instruction
+  ///
+  fn replace(&mut self, ix: &Instruction) -> ProgramResult {
+    let authority = self.authority;
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::ReplaceProposalInstruction {
+        caller: authority,
+        proposal_instruction: self.proposal_instruction,
+        invoked_program: ix.program_id,
+        proposal: self.proposal,
+        dao: self.dao,
+        system_program: system_program::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::ReplaceProposalInstruction {
+        data: ix.data.clone(),
+        pubkeys: ix.accounts.iter().map(|meta| meta.pubkey).collect(),
+        is_signers: ix.accounts.iter().map(|meta| meta.is_signer).collect(),
+        is_writables: ix.accounts.iter().map(|meta| meta.is_writable).collect(),
+        is_masters: ix
+          .accounts
+          .iter()
+          .map(|meta| meta.pubkey == self.master)
+          .collect(),
+        tx_idx: 0,
+      }
+      .data(),
+    })
+  }
+
+  ///
+  /// Remove the instruction to execute, re-indexing the following ones
+  ///
+  fn remove(&mut self, following: &[Pubkey]) -> ProgramResult {
+    let mut accounts = inter_dao::accounts::RemoveProposalInstruction {
+      caller: self.authority,
+      proposal_instruction: self.proposal_instruction,
+      proposal: self.proposal,
+      dao: self.dao,
+    }
+    .to_account_metas(None);
+    accounts.extend(
+      following
+        .iter()
+        .map(|pubkey| AccountMeta::new(*pubkey, false)),
+    );
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts,
+      data: inter_dao::instruction::RemoveProposalInstruction {}.data(),
+    })
+  }
+
+  fn fail(&mut self) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::FailProposalInstruction {
+        caller: self.voter,
+        proposal: self.proposal,
+        proposal_instruction: self.proposal_instruction,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::FailProposalInstruction {}.data(),
+    })
+  }
+
+  fn update_retry_window(&mut self, retry_window: i64) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::UpdateProposalBounds {
+        authority: self.authority,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::UpdateProposalBounds {
+        min_voting_duration: 60,
+        max_voting_duration: 86_400,
+        min_notice_period: NOTICE,
+        execution_window: EXECUTION_WINDOW,
+        retry_window,
+      }
+      .data(),
+    })
+  }
+
+  fn close(&mut self) -> ProgramResult {
+    let treasurer = self.treasurer();
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::Close {
+        authority: self.voter,
+        dst: self.voter_token_account(),
+        treasurer,
+        mint: self.mint,
+        treasury: self.token_account(&treasurer),
+        proposal: self.proposal,
+        dao: self.dao,
+        receipt: self.receipt(0),
+        token_program: self.token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::Close {}.data(),
+    })
+  }
+
+  fn escrow(&self) -> Pubkey {
+    Pubkey::find_program_address(
+      &[
+        b"escrow",
+        self.dao.as_ref(),
+        self.mint.as_ref(),
+        self.voter.as_ref(),
+      ],
+      &inter_dao::ID,
+    )
+    .0
+  }
+
+  fn escrow_treasurer(&self) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow_treasurer", self.dao.as_ref()], &inter_dao::ID).0
+  }
+
+  fn escrow_receipt(&self) -> Pubkey {
+    Pubkey::find_program_address(
+      &[
+        b"escrow_receipt",
+        self.proposal.as_ref(),
+        self.escrow().as_ref(),
+      ],
+      &inter_dao::ID,
+    )
+    .0
+  }
+
+  fn lock(&mut self, amount: u64, unlocked_date: i64) -> ProgramResult {
+    let escrow_treasurer = self.escrow_treasurer();
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::LockEscrow {
+        authority: self.voter,
+        src: self.voter_token_account(),
+        escrow_treasurer,
+        mint: self.mint,
+        escrow_treasury: self.token_account(&escrow_treasurer),
+        dao: self.dao,
+        escrow: self.escrow(),
+        token_program: self.token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::LockEscrow {
+        amount,
+        unlocked_date,
+      }
+      .data(),
+    })
+  }
+
+  fn withdraw(&mut self) -> ProgramResult {
+    let escrow_treasurer = self.escrow_treasurer();
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::WithdrawEscrow {
+        authority: self.voter,
+        dst: self.voter_token_account(),
+        escrow_treasurer,
+        mint: self.mint,
+        escrow_treasury: self.token_account(&escrow_treasurer),
+        dao: self.dao,
+        escrow: self.escrow(),
+        token_program: self.token_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::WithdrawEscrow {}.data(),
+    })
+  }
+
+  fn vote_escrow_for(&mut self) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::VoteEscrowFor {
+        authority: self.voter,
+        escrow: self.escrow(),
+        proposal: self.proposal,
+        dao: self.dao,
+        receipt: self.escrow_receipt(),
+        voter_record: self.voter_record(),
+        taxman: self.authority,
+        revenueman: self.authority,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::VoteEscrowFor { tax: 0, revenue: 0 }.data(),
+    })
+  }
+
+  fn close_escrow_voting(&mut self) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::CloseEscrowVoting {
+        authority: self.voter,
+        proposal: self.proposal,
+        dao: self.dao,
+        receipt: self.escrow_receipt(),
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::CloseEscrowVoting {}.data(),
+    })
+  }
+
+  fn update_council(&mut self, council_mint: Option<Pubkey>, council_supply: u64) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::UpdateDaoCouncil {
+        authority: self.authority,
+        dao: self.dao,
+        council_mint,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::UpdateDaoCouncil { council_supply }.data(),
+    })
+  }
+
+  fn transfer_authority(&mut self, authority: Pubkey, new_authority: Pubkey) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::TransferAuthority {
+        authority,
+        new_authority,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::TransferAuthority {}.data(),
+    })
+  }
+
+  fn accept_authority(&mut self, new_authority: Pubkey) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::AcceptAuthority {
+        new_authority,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::AcceptAuthority {}.data(),
+    })
+  }
+
+  fn cancel_authority_transfer(&mut self) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::CancelAuthorityTransfer {
+        authority: self.authority,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::CancelAuthorityTransfer {}.data(),
+    })
+  }
+
+  fn renounce_authority(&mut self) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::RenounceAuthority {
+        authority: self.authority,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::RenounceAuthority {}.data(),
+    })
+  }
+
+  fn dao(&self) -> Dao {
+    self.bank.get(&self.dao)
+  }
+
+  fn update_one_direction_voting(&mut self, one_direction_voting: bool) {
+    self
+      .bank
+      .process(Instruction {
+        program_id: inter_dao::ID,
+        accounts: inter_dao::accounts::UpdateOneDirectionVoting {
+          authority: self.authority,
+          dao: self.dao,
+        }
+        .to_account_metas(None),
+        data: inter_dao::instruction::UpdateOneDirectionVoting {
+          one_direction_voting,
+        }
+        .data(),
+      })
+      .unwrap();
+  }
+
+  fn update_snapshot_voting(&mut self, snapshot_voting: bool) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::UpdateSnapshotVoting {
+        authority: self.authority,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::UpdateSnapshotVoting { snapshot_voting }.data(),
+    })
+  }
+
+  fn update_timelock(&mut self, timelock: i64) {
+    self.update_guardians(vec![], 0, timelock).unwrap();
+  }
+
+  fn update_guardians(
+    &mut self,
+    guardians: Vec<Pubkey>,
+    veto_threshold: u8,
+    timelock: i64,
+  ) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::UpdateDaoGuardians {
+        authority: self.authority,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::UpdateDaoGuardians {
+        guardians,
+        veto_threshold,
+        timelock,
+      }
+      .data(),
+    })
+  }
+
+  fn update_regime(&mut self, regime: DaoRegime) {
+    self
+      .bank
+      .process(Instruction {
+        program_id: inter_dao::ID,
+        accounts: inter_dao::accounts::UpdateDaoRegime {
+          authority: self.authority,
+          dao: self.dao,
+        }
+        .to_account_metas(None),
+        data: inter_dao::instruction::UpdateDaoRegime { regime }.data(),
+      })
+      .unwrap();
+  }
+
+  fn veto(&mut self, caller: Pubkey) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::VetoProposal {
+        caller,
+        proposal: self.proposal,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::VetoProposal {}.data(),
+    })
+  }
+
+  fn update_early_finalization(&mut self, early_finalization: bool) {
+    self
+      .bank
+      .process(Instruction {
+        program_id: inter_dao::ID,
+        accounts: inter_dao::accounts::UpdateEarlyFinalization {
+          authority: self.authority,
+          dao: self.dao,
+        }
+        .to_account_metas(None),
+        data: inter_dao::instruction::UpdateEarlyFinalization { early_finalization }.data(),
+      })
+      .unwrap();
+  }
+
+  fn finalize(&mut self) -> ProgramResult {
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::FinalizeProposal {
+        caller: self.voter,
+        proposal: self.proposal,
+        dao: self.dao,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::FinalizeProposal {}.data(),
+    })
+  }
+
+  fn status(&mut self) -> ProposalStatus {
+    self
+      .bank
+      .process(Instruction {
+        program_id: inter_dao::ID,
+        accounts: inter_dao::accounts::GetProposalStatus {
+          proposal: self.proposal,
+          dao: self.dao,
+        }
+        .to_account_metas(None),
+        data: inter_dao::instruction::GetProposalStatus {}.data(),
+      })
+      .unwrap();
+    let (program_id, data) = self.bank.return_data().unwrap();
+    assert_eq!(program_id, inter_dao::ID);
+    ProposalStatus::try_from_slice(&data).unwrap()
+  }
+
+  fn proposal(&self) -> Proposal {
+    self.bank.get(&self.proposal)
+  }
+
+  fn content(&self, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+      &[b"content", &nonce.to_le_bytes(), self.dao.as_ref()],
+      &inter_dao::ID,
+    )
+    .0
+  }
+
+  fn edit(&mut self, authority: Pubkey, content: Pubkey, metadata_uri: &str) -> ProgramResult {
+    let discriminator = self.bank.get::<Content>(&content).discriminator;
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::EditContent {
+        authority,
+        content,
+        system_program: system_program::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::EditContent {
+        metadata: [discriminator[0] + 1; 32],
+        hash_algorithm: HashAlgorithm::Blake3,
+        metadata_uri: metadata_uri.to_string(),
+      }
+      .data(),
+    })
+  }
+
+  ///
+  /// Post at the next content nonce of the DAO
+  ///
+  fn post(
+    &mut self,
+    authority: Pubkey,
+    discriminator: [u8; 8],
+    proposal: Option<Pubkey>,
+    parent: Option<Pubkey>,
+  ) -> ProgramResult {
+    let holdings = self.token_account(&authority);
+    let content = self.content(self.dao().content_nonce);
+    self.bank.process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::InitializeContent {
+        authority,
+        content,
+        dao: self.dao,
+        proposal,
+        parent,
+        holdings: self.bank.account(&holdings).map(|_| holdings),
+        system_program: system_program::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::InitializeContent {
+        discriminator,
+        metadata: [discriminator[0]; 32],
+        hash_algorithm: HashAlgorithm::Sha256,
+        metadata_uri: String::new(),
+      }
+      .data(),
+    })
+  }
+}
+
+#[test]
+fn test_full_governance_flow() {
+  let mut fixture = Fixture::new();
+  let amount = SUPPLY * 3 / 5;
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(amount).unwrap();
+  assert_eq!(
+    fixture.bank.token_balance(&fixture.voter_token_account()),
+    0
+  );
+  assert_eq!(fixture.proposal().voting_for_power, amount as u128);
+
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let balance = fixture.bank.lamports(&fixture.recipient);
+  fixture.execute().unwrap();
+  assert_eq!(
+    fixture.bank.lamports(&fixture.recipient),
+    balance + TRANSFERRED
+  );
+  let proposal = fixture.proposal();
+  assert!(proposal.executed);
+  assert_eq!(proposal.state, ProposalState::Executed);
+  assert_eq!(fixture.execute(), error(ErrorCode::ExecutedProposal));
+
+  fixture.close().unwrap();
+  assert_eq!(
+    fixture.bank.token_balance(&fixture.voter_token_account()),
+    amount
+  );
+}
+
+#[test]
+fn test_proposal_status_view() {
+  let mut fixture = Fixture::new();
+  let amount = SUPPLY * 3 / 5;
+
+  let status = fixture.status();
+  assert_eq!(status.state, ProposalState::Draft);
+  assert!(!status.is_passed);
+  assert_eq!(status.total_power, SUPPLY as u128);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(amount).unwrap();
+  let status = fixture.status();
+  assert_eq!(status.state, ProposalState::Voting);
+  assert_eq!(status.voting_for_power, amount as u128);
+  assert!(!status.is_passed);
+
+  // The view evaluates the outcome without recording it
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let status = fixture.status();
+  assert_eq!(status.state, ProposalState::Succeeded);
+  assert!(status.is_passed);
+  assert!(!status.is_final);
+  assert_eq!(status.timestamp, fixture.end_date + 1);
+  assert_eq!(fixture.proposal().state, ProposalState::Voting);
+}
+
+#[test]
+fn test_receipt_indices_are_assigned() {
+  let mut fixture = Fixture::new();
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY / 5).unwrap();
+  fixture.vote_for(SUPPLY / 5).unwrap();
+
+  let voter_record: VoterRecord = fixture.bank.get(&fixture.voter_record());
+  assert_eq!(voter_record.authority, fixture.voter);
+  assert_eq!(voter_record.receipts, 2);
+  for index in 0..voter_record.receipts {
+    let receipt: Receipt = fixture.bank.get(&fixture.receipt(index));
+    assert_eq!(receipt.index, index);
+    assert_eq!(receipt.amount, SUPPLY / 5);
+  }
+  assert_eq!(
+    fixture.proposal().voting_for_power,
+    (SUPPLY * 2 / 5) as u128
+  );
+}
+
+#[test]
+fn test_one_direction_voting() {
+  let mut fixture = Fixture::new();
+  let unrestricted = fixture.proposal;
+  fixture.update_one_direction_voting(true);
+  fixture.initialize_proposal(1);
+  assert!(fixture.proposal().one_direction_voting);
+  let unlocked_date = fixture.bank.now() + Escrow::MAX_LOCK_DURATION;
+  fixture.lock(100, unlocked_date).unwrap();
+
+  // The first vote pins the direction of every later deposit or escrow vote
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(100).unwrap();
+  assert_eq!(
+    fixture.vote_against(100),
+    error(ErrorCode::ConflictingVoteDirection)
+  );
+  fixture.vote_for(100).unwrap();
+  fixture.vote_escrow_for().unwrap();
+  let voter_record: VoterRecord = fixture.bank.get(&fixture.voter_record());
+  assert_eq!(voter_record.authority, fixture.voter);
+  assert_eq!(voter_record.proposal, fixture.proposal);
+  assert_eq!(voter_record.action, ReceiptAction::VoteFor);
+  assert_eq!(voter_record.receipts, 2);
+
+  // Proposals snapshot the rule at their creation
+  fixture.proposal = unrestricted;
+  assert!(!fixture.proposal().one_direction_voting);
+  fixture.vote_for(100).unwrap();
+  fixture.vote_against(100).unwrap();
+  let voter_record: VoterRecord = fixture.bank.get(&fixture.voter_record());
+  assert_eq!(voter_record.receipts, 2);
+}
+
+#[test]
+fn test_increase_vote() {
+  let mut fixture = Fixture::new();
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(100).unwrap();
+  fixture.vote_against(100).unwrap();
+
+  // Increases add to the receipt and keep its direction
+  fixture.increase_vote(0, 150).unwrap();
+  fixture.increase_vote(1, 50).unwrap();
+  let receipt: Receipt = fixture.bank.get(&fixture.receipt(0));
+  assert_eq!(receipt.amount, 250);
+  assert_eq!(receipt.power, 250);
+  let receipt: Receipt = fixture.bank.get(&fixture.receipt(1));
+  assert_eq!(receipt.amount, 150);
+  assert_eq!(receipt.action, ReceiptAction::VoteAgainst);
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.voting_for_power, 250);
+  assert_eq!(proposal.voting_against_power, 150);
+  let treasury = fixture.token_account(&fixture.treasurer());
+  assert_eq!(fixture.bank.token_balance(&treasury), 400);
+
+  // Votes can only be increased while voting
+  fixture.bank.warp_to(fixture.end_date + 1);
+  assert_eq!(
+    fixture.increase_vote(0, 10),
+    error(ErrorCode::EndedProposal)
+  );
+}
+
+#[test]
+fn test_voting_window_boundaries() {
+  let mut fixture = Fixture::new();
+
+  fixture.bank.warp_to(fixture.start_date - 1);
+  assert_eq!(fixture.vote_for(1), error(ErrorCode::NotStartedProposal));
+  fixture.bank.warp_to(fixture.end_date + 1);
+  assert_eq!(fixture.vote_for(1), error(ErrorCode::EndedProposal));
+  // Both ends are inclusive
+  fixture.bank.warp_to(fixture.end_date);
+  fixture.vote_for(1).unwrap();
+}
+
+#[test]
+fn test_close_before_the_end() {
+  let mut fixture = Fixture::new();
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY / 10).unwrap();
+  assert_eq!(fixture.close(), error(ErrorCode::NotEndedProposal));
+  fixture.bank.warp_to(fixture.end_date + 1);
+  fixture.close().unwrap();
+}
+
+#[test]
+fn test_defeated_proposal() {
+  let mut fixture = Fixture::new();
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY / 2).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  assert_eq!(fixture.execute(), error(ErrorCode::NotConsentedProposal));
+  // Failed transactions leave no trace, so the outcome is recorded by finalization
+  assert_eq!(fixture.proposal().state, ProposalState::Voting);
+  fixture.finalize().unwrap();
+  assert_eq!(fixture.proposal().state, ProposalState::Defeated);
+}
+
+#[test]
+fn test_timelock_boundaries() {
+  let mut fixture = Fixture::new();
+  let timelock = 50;
+  fixture.update_timelock(timelock);
+  // The timelock is snapshotted at creation
+  fixture.initialize_proposal(1);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + timelock);
+  assert_eq!(fixture.execute(), error(ErrorCode::TimelockedProposal));
+  fixture.bank.warp_to(fixture.end_date + timelock + 1);
+  fixture.execute().unwrap();
+}
+
+#[test]
+fn test_early_pass_executes_before_the_end() {
+  let mut fixture = Fixture::new();
+  let timelock = 50;
+  fixture.update_timelock(timelock);
+  fixture.update_early_finalization(true);
+  fixture.initialize_proposal(1);
+  let voter = fixture.voter;
+  fixture
+    .bank
+    .create_token_account(&fixture.mint, &voter, SUPPLY);
+
+  // The remaining supply can't flip the outcome anymore
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 4 / 5).unwrap();
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.decided_date, fixture.start_date);
+  assert_eq!(fixture.vote_for(1), error(ErrorCode::EndedProposal));
+
+  // The timelock runs from the decision instead of the end date
+  fixture.bank.warp_to(fixture.start_date + timelock);
+  assert_eq!(fixture.execute(), error(ErrorCode::TimelockedProposal));
+  fixture.bank.warp_to(fixture.start_date + timelock + 1);
+  assert!(fixture.bank.now() < fixture.end_date);
+  fixture.execute().unwrap();
+  assert_eq!(fixture.proposal().state, ProposalState::Executed);
+}
+
+#[test]
+fn test_early_fail_is_defeated_before_the_end() {
+  let mut fixture = Fixture::new();
+  fixture.update_early_finalization(true);
+  fixture.initialize_proposal(1);
+
+  // The remaining supply can't reach the quorum anymore
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_against(SUPPLY / 4).unwrap();
+  fixture.finalize().unwrap();
+  assert_eq!(fixture.proposal().state, ProposalState::Defeated);
+  assert!(fixture.bank.now() < fixture.end_date);
+}
+
+#[test]
+fn test_decisive_votes_wait_without_early_finalization() {
+  let mut fixture = Fixture::new();
+  let voter = fixture.voter;
+  fixture
+    .bank
+    .create_token_account(&fixture.mint, &voter, SUPPLY);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 4 / 5).unwrap();
+  fixture.vote_for(1).unwrap();
+  assert_eq!(fixture.proposal().decided_date, 0);
+  assert_eq!(fixture.execute(), error(ErrorCode::NotConsentedProposal));
+  fixture.bank.warp_to(fixture.end_date + 1);
+  fixture.execute().unwrap();
+}
+
+///
+/// A proposal whose second instruction transfers more SOL than the master holds
+///
+fn failing_fixture() -> (Fixture, Pubkey) {
+  let mut fixture = Fixture::new();
+  let (authority, recipient) = (fixture.authority, fixture.recipient);
+  let first = fixture.proposal_instruction;
+  let kind = ProposalInstructionKind::SolTransfer {
+    dst: recipient,
+    lamports: 100 * SOL,
+  };
+  fixture.add_template(authority, kind, None).unwrap();
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let failing = fixture.proposal_instruction;
+  fixture.proposal_instruction = first;
+  fixture.execute().unwrap();
+  fixture.proposal_instruction = failing;
+  assert!(fixture.execute().is_err());
+  (fixture, failing)
+}
+
+#[test]
+fn test_failing_instruction_is_retried() {
+  let (mut fixture, _) = failing_fixture();
+
+  // Nobody can fail the instruction while executors may retry
+  fixture.bank.warp(RETRY_WINDOW);
+  assert_eq!(
+    fixture.fail(),
+    error(ErrorCode::RetryableProposalInstruction)
+  );
+  let master = fixture.master;
+  fixture.bank.airdrop(&master, 100 * SOL);
+  fixture.execute().unwrap();
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.total_failed, 0);
+  assert_eq!(proposal.state, ProposalState::Executed);
+}
+
+#[test]
+fn test_failing_instruction_is_failed_after_the_retry_window() {
+  let (mut fixture, failing) = failing_fixture();
+
+  // The retry window runs from the last execution
+  fixture.bank.warp(RETRY_WINDOW + 1);
+  fixture.fail().unwrap();
+  let proposal_instruction: ProposalInstruction = fixture.bank.get(&failing);
+  assert!(proposal_instruction.failed);
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.total_failed, 1);
+  assert_eq!(proposal.state, ProposalState::Executed);
+  assert_eq!(fixture.execute(), error(ErrorCode::ExecutedProposal));
+  assert_eq!(fixture.fail(), error(ErrorCode::ExecutedProposal));
+}
+
+///
+/// A proposal of independent instructions, the first of which transfers more SOL than the master holds
+///
+fn independent_fixture(following: &[u64]) -> (Fixture, Vec<Pubkey>) {
+  let mut fixture = Fixture::new();
+  fixture.independent_instructions = true;
+  fixture.initialize_proposal(1);
+  let (authority, recipient, master) = (fixture.authority, fixture.recipient, fixture.master);
+  let mut proposal_instructions = vec![fixture.proposal_instruction];
+  fixture
+    .replace(&system_instruction::transfer(
+      &master,
+      &recipient,
+      100 * SOL,
+    ))
+    .unwrap();
+  for &lamports in following {
+    let kind = ProposalInstructionKind::SolTransfer {
+      dst: recipient,
+      lamports,
+    };
+    fixture.add_template(authority, kind, None).unwrap();
+    proposal_instructions.push(fixture.proposal_instruction);
+  }
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  fixture.proposal_instruction = proposal_instructions[0];
+  assert!(fixture.execute().is_err());
+  (fixture, proposal_instructions)
+}
+
+#[test]
+fn test_failed_independent_instruction_is_skipped() {
+  let (mut fixture, proposal_instructions) = independent_fixture(&[TRANSFERRED]);
+
+  fixture.bank.warp(RETRY_WINDOW + 1);
+  fixture.fail().unwrap();
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.total_failed, 1);
+  assert!(!proposal.executed);
+  assert_eq!(proposal.state, ProposalState::Executing);
+  // A processed proposal doesn't expire, even if its only processed instruction failed
+  fixture
+    .bank
+    .warp_to(fixture.end_date + EXECUTION_WINDOW + 1);
+  assert_eq!(fixture.status().state, ProposalState::Executing);
+  fixture.proposal_instruction = proposal_instructions[1];
+  fixture.execute().unwrap();
+  let proposal = fixture.proposal();
+  assert_eq!((proposal.total_executed, proposal.total_failed), (1, 1));
+  assert_eq!(proposal.state, ProposalState::Executed);
+  let recipient = fixture.recipient;
+  assert_eq!(
+    fixture.bank.account(&recipient).unwrap().lamports,
+    TRANSFERRED
+  );
+}
+
+#[test]
+fn test_each_independent_instruction_has_its_own_retry_window() {
+  let (mut fixture, proposal_instructions) = independent_fixture(&[100 * SOL]);
+
+  fixture.bank.warp(RETRY_WINDOW + 1);
+  fixture.fail().unwrap();
+  // The retry window of the next instruction runs from the previous failure
+  fixture.proposal_instruction = proposal_instructions[1];
+  assert!(fixture.execute().is_err());
+  assert_eq!(
+    fixture.fail(),
+    error(ErrorCode::RetryableProposalInstruction)
+  );
+  fixture.bank.warp(RETRY_WINDOW);
+  assert_eq!(
+    fixture.fail(),
+    error(ErrorCode::RetryableProposalInstruction)
+  );
+  fixture.bank.warp(1);
+  fixture.fail().unwrap();
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.total_failed, 2);
+  assert_eq!(proposal.state, ProposalState::Executed);
+}
+
+#[test]
+fn test_retry_window_bounds() {
+  let mut fixture = Fixture::new();
+  let invalid = error(ErrorCode::InvalidProposalBounds);
+
+  assert_eq!(fixture.update_retry_window(0), invalid);
+  assert_eq!(
+    fixture.update_retry_window(Dao::MIN_RETRY_WINDOW - 1),
+    invalid
+  );
+  // Failing the first instruction must remain possible within the execution window
+  assert_eq!(fixture.update_retry_window(EXECUTION_WINDOW), invalid);
+  fixture.update_retry_window(Dao::MIN_RETRY_WINDOW).unwrap();
+}
+
+#[test]
+fn test_remove_proposal_instruction_by_index() {
+  let mut fixture = Fixture::new();
+  let (authority, recipient) = (fixture.authority, fixture.recipient);
+  let first = fixture.proposal_instruction;
+  let mut following = Vec::new();
+  for lamports in [SOL / 4, SOL / 8] {
+    let kind = ProposalInstructionKind::SolTransfer {
+      dst: recipient,
+      lamports,
+    };
+    fixture.add_template(authority, kind, None).unwrap();
+    following.push(fixture.proposal_instruction);
+  }
+  let (second, third) = (following[0], following[1]);
+
+  // Every following instruction must be passed in index order
+  fixture.proposal_instruction = first;
+  assert_eq!(
+    fixture.remove(&[third]),
+    error(ErrorCode::MissingFollowingProposalInstructions)
+  );
+  assert_eq!(
+    fixture.remove(&[third, second]),
+    error(ErrorCode::InvalidProposalIdx)
+  );
+  fixture.remove(&[second, third]).unwrap();
+  assert!(fixture.bank.account(&first).is_none());
+  assert_eq!(fixture.proposal().total_instruction, 2);
+  let proposal_instruction: ProposalInstruction = fixture.bank.get(&second);
+  assert_eq!(proposal_instruction.index, 0);
+  let proposal_instruction: ProposalInstruction = fixture.bank.get(&third);
+  assert_eq!(proposal_instruction.index, 1);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let balance = fixture.bank.lamports(&recipient);
+  for proposal_instruction in [second, third] {
+    fixture.proposal_instruction = proposal_instruction;
+    fixture.execute().unwrap();
+  }
+  assert_eq!(
+    fixture.bank.lamports(&recipient),
+    balance + SOL / 4 + SOL / 8
+  );
+  assert_eq!(fixture.proposal().state, ProposalState::Executed);
+}
+
+#[test]
+fn test_proposal_instruction_edits_are_bound_to_the_dao() {
+  let mut fixture = Fixture::new();
+  let (dao, master, recipient) = (fixture.dao, fixture.master, fixture.recipient);
+  let transfer = system_instruction::transfer(&master, &recipient, SOL / 4);
+
+  // Another DAO of the same program cannot stand in for the proposal's
+  let other = Pubkey::new_unique();
+  let account = fixture.bank.account(&dao).cloned().unwrap();
+  fixture.bank.set_account(other, account);
+  fixture.dao = other;
+  let mismatch = anchor_error(anchor_lang::error::ErrorCode::ConstraintHasOne);
+  assert_eq!(fixture.replace(&transfer), mismatch);
+  assert_eq!(fixture.remove(&[]), mismatch);
+
+  fixture.dao = dao;
+  fixture.replace(&transfer).unwrap();
+  let proposal_instruction: ProposalInstruction = fixture.bank.get(&fixture.proposal_instruction);
+  assert_eq!(proposal_instruction.kind, ProposalInstructionKind::Raw);
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let balance = fixture.bank.lamports(&recipient);
+  fixture.execute().unwrap();
+  assert_eq!(fixture.bank.lamports(&recipient), balance + SOL / 4);
+}
+
+#[test]
+fn test_escrow_lock_vote_and_withdraw() {
+  let mut fixture = Fixture::new();
+  let now = fixture.bank.now();
+  let unlocked_date = now + Escrow::MAX_LOCK_DURATION;
+  let amount = SUPPLY * 3 / 5;
+
+  let invalid = error(ErrorCode::InvalidLockDuration);
+  assert_eq!(fixture.lock(amount, now), invalid);
+  assert_eq!(fixture.lock(amount, unlocked_date + 1), invalid);
+  fixture.lock(amount, unlocked_date).unwrap();
+  let escrow_treasury = fixture.token_account(&fixture.escrow_treasurer());
+  assert_eq!(fixture.bank.token_balance(&escrow_treasury), amount);
+  assert_eq!(
+    fixture.bank.token_balance(&fixture.voter_token_account()),
+    0
+  );
+  // Locks can only be extended
+  assert_eq!(fixture.lock(0, unlocked_date - 1), invalid);
+
+  // The power is snapshotted at the proposal start and decays with the remaining lock
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_escrow_for().unwrap();
+  let escrow: Escrow = fixture.bank.get(&fixture.escrow());
+  let power = escrow.voting_power_at(fixture.start_date).unwrap();
+  assert!(power > 0 && power < amount);
+  assert_eq!(escrow.voting_until, fixture.end_date);
+  let receipt: Receipt = fixture.bank.get(&fixture.escrow_receipt());
+  assert_eq!(receipt.escrow, fixture.escrow());
+  assert_eq!(fixture.proposal().voting_for_power, u128::from(power));
+  assert!(fixture.bank.account(&fixture.voter_record()).is_some());
+
+  // Escrow receipts are closed once the vote ends, refunding their rent
+  assert_eq!(
+    fixture.close_escrow_voting(),
+    error(ErrorCode::NotEndedProposal)
+  );
+  assert_eq!(fixture.withdraw(), error(ErrorCode::LockedEscrow));
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let voter = fixture.voter;
+  let balance = fixture.bank.lamports(&voter);
+  let rent = fixture.bank.lamports(&fixture.escrow_receipt());
+  fixture.close_escrow_voting().unwrap();
+  assert!(fixture.bank.account(&fixture.escrow_receipt()).is_none());
+  assert_eq!(fixture.bank.lamports(&voter), balance + rent);
+
+  // Tokens stay locked until the unlocked date
+  assert_eq!(fixture.withdraw(), error(ErrorCode::LockedEscrow));
+  fixture.bank.warp_to(unlocked_date);
+  fixture.withdraw().unwrap();
+  assert!(fixture.bank.account(&fixture.escrow()).is_none());
+  assert_eq!(fixture.bank.token_balance(&escrow_treasury), 0);
+  assert_eq!(
+    fixture.bank.token_balance(&fixture.voter_token_account()),
+    amount
+  );
+}
+
+#[test]
+fn test_snapshot_voting_refuses_tokens_acquired_after_the_start() {
+  let mut fixture = Fixture::new();
+  fixture.update_snapshot_voting(true).unwrap();
+  fixture.initialize_proposal(1);
+  assert!(fixture.proposal().snapshot_voting);
+  let unlocked_date = fixture.bank.now() + Escrow::MAX_LOCK_DURATION;
+  fixture.lock(SUPPLY / 5, unlocked_date).unwrap();
+
+  // Tokens acquired after the start date neither vote by deposit nor add escrow power
+  fixture.bank.warp_to(fixture.start_date + 1);
+  let refused = error(ErrorCode::SnapshotVoting);
+  assert_eq!(fixture.vote_for(SUPPLY / 5), refused);
+  assert_eq!(fixture.vote_against(SUPPLY / 5), refused);
+  fixture.lock(SUPPLY * 2 / 5, unlocked_date).unwrap();
+  fixture.vote_escrow_for().unwrap();
+  let escrow: Escrow = fixture.bank.get(&fixture.escrow());
+  let power = escrow.voting_power_at(fixture.start_date).unwrap();
+  assert!(power > 0 && power <= SUPPLY / 5);
+  assert_eq!(fixture.proposal().voting_for_power, u128::from(power));
+
+  // NFT votes can't be snapshotted by the escrow
+  let (authority, mint) = (fixture.authority, fixture.mint);
+  fixture.dao = initialize_dao(&mut fixture.bank, &authority, &mint, true);
+  assert_eq!(
+    fixture.update_snapshot_voting(true),
+    error(ErrorCode::UnsupportedNftSnapshotVoting)
+  );
+}
+
+#[test]
+fn test_escrow_checkpoints_are_merged_once_full() {
+  let mut fixture = Fixture::new();
+  let locked_date = fixture.bank.now();
+  let unlocked_date = locked_date + Escrow::MAX_LOCK_DURATION;
+  fixture.lock(100, unlocked_date).unwrap();
+  let escrow: Escrow = fixture.bank.get(&fixture.escrow());
+  let power = escrow.voting_power_at(fixture.start_date).unwrap();
+
+  // Top up more times than there are checkpoints once the proposal started
+  fixture.bank.warp_to(fixture.start_date);
+  for _ in 0..Escrow::MAX_CHECKPOINTS {
+    fixture.bank.warp(1);
+    fixture.lock(1, unlocked_date).unwrap();
+  }
+  let escrow: Escrow = fixture.bank.get(&fixture.escrow());
+  assert_eq!(escrow.checkpoints.len(), Escrow::MAX_CHECKPOINTS);
+  assert_eq!(escrow.checkpoints[0].timestamp, locked_date);
+  assert_eq!(escrow.amount, 100 + Escrow::MAX_CHECKPOINTS as u64);
+
+  // The lock preceding the snapshot still backs the vote
+  fixture.vote_escrow_for().unwrap();
+  assert_eq!(fixture.proposal().voting_for_power, u128::from(power));
+}
+
+///
+/// A dual-house DAO whose council is held by the voter and the authority
+///
+fn council_fixture() -> Fixture {
+  let mut fixture = Fixture::new();
+  let (authority, voter) = (fixture.authority, fixture.voter);
+  let council = fixture.bank.create_mint(&authority, 0, 0);
+  fixture.bank.create_token_account(&council, &voter, 40);
+  fixture.bank.create_token_account(&council, &authority, 20);
+  fixture.update_council(Some(council), 100).unwrap();
+  fixture.initialize_proposal(1);
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.council_mint, council);
+  assert_eq!(proposal.council_supply, 100);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.mint = council;
+  fixture.vote_for(40).unwrap();
+  fixture
+}
+
+#[test]
+fn test_council_tallies_separately() {
+  let mut fixture = council_fixture();
+  let (voter, authority) = (fixture.voter, fixture.authority);
+  fixture.voter = authority;
+  fixture.vote_for(20).unwrap();
+  fixture.voter = voter;
+
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.voting_for_power, u128::from(SUPPLY * 3 / 5));
+  assert_eq!(proposal.council_for_power, 60);
+  let status = fixture.status();
+  assert_eq!(status.council_for_power, 60);
+  assert_eq!(status.council_total_power, 100);
+  fixture.bank.warp_to(fixture.end_date + 1);
+  fixture.execute().unwrap();
+  assert_eq!(fixture.proposal().state, ProposalState::Executed);
+}
+
+#[test]
+fn test_council_must_consent() {
+  let mut fixture = council_fixture();
+  // The community passed, but the council holds only 40 of 100
+  fixture.bank.warp_to(fixture.end_date + 1);
+  assert_eq!(fixture.execute(), error(ErrorCode::NotConsentedProposal));
+  let status = fixture.status();
+  assert_eq!(status.state, ProposalState::Defeated);
+  assert_eq!(status.council_for_power, 40);
+}
+
+#[test]
+fn test_council_validation() {
+  let mut fixture = Fixture::new();
+  let (authority, mint) = (fixture.authority, fixture.mint);
+  assert_eq!(
+    fixture.update_council(Some(mint), 100),
+    error(ErrorCode::InvalidCouncilMint)
+  );
+  // A council without supply would divide its tally by zero
+  let council = fixture.bank.create_mint(&authority, 0, 0);
+  assert_eq!(
+    fixture.update_council(Some(council), 0),
+    error(ErrorCode::InvalidCouncilMint)
+  );
+
+  // NFT DAOs vote by collection, so no council mint applies
+  fixture.dao = initialize_dao(&mut fixture.bank, &authority, &mint, true);
+  assert_eq!(
+    fixture.update_council(Some(council), 100),
+    error(ErrorCode::UnsupportedNftCouncil)
+  );
+  fixture.update_council(None, 0).unwrap();
+}
+
+#[test]
+fn test_guardian_vetoes() {
+  let mut fixture = Fixture::new();
+  let timelock = 50;
+  let guardians: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+  fixture
+    .update_guardians(guardians.clone(), 2, timelock)
+    .unwrap();
+  fixture.initialize_proposal(1);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  assert_eq!(
+    fixture.veto(guardians[0]),
+    error(ErrorCode::NotEndedProposal)
+  );
+  fixture.bank.warp_to(fixture.end_date + 1);
+  assert_eq!(fixture.status().state, ProposalState::Queued);
+  // Only guardians veto in autonomous DAOs, and each of them counts once
+  let authority = fixture.authority;
+  assert_eq!(fixture.veto(authority), error(ErrorCode::NoPermission));
+  fixture.veto(guardians[0]).unwrap();
+  fixture.veto(guardians[0]).unwrap();
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.vetoes.count_ones(), 1);
+  assert!(!proposal.vetoed);
+  fixture.veto(guardians[2]).unwrap();
+  let proposal = fixture.proposal();
+  assert!(proposal.vetoed);
+  assert_eq!(proposal.state, ProposalState::Cancelled);
+  assert_eq!(fixture.veto(guardians[1]), error(ErrorCode::VetoedProposal));
+  fixture.bank.warp_to(fixture.end_date + timelock + 1);
+  assert_eq!(fixture.execute(), error(ErrorCode::VetoedProposal));
+}
+
+#[test]
+fn test_duplicate_guardians_are_rejected() {
+  let mut fixture = Fixture::new();
+  let guardian = Pubkey::new_unique();
+  // A duplicate could never set its own veto bit, so the threshold would be out of reach
+  assert_eq!(
+    fixture.update_guardians(vec![guardian, guardian], 2, 50),
+    error(ErrorCode::InvalidGuardians)
+  );
+  assert_eq!(
+    fixture.update_guardians(vec![guardian, Pubkey::new_unique(), guardian], 1, 50),
+    error(ErrorCode::InvalidGuardians)
+  );
+  assert!(fixture.dao().guardians.is_empty());
+}
+
+#[test]
+fn test_guardians_are_snapshotted_at_creation() {
+  let mut fixture = Fixture::new();
+  let timelock = 50;
+  let (former, newcomer) = (Pubkey::new_unique(), Pubkey::new_unique());
+  fixture.update_guardians(vec![former], 1, timelock).unwrap();
+  fixture.initialize_proposal(1);
+  // Guardians appointed later can't reach the running proposal, dismissed ones still can
+  fixture
+    .update_guardians(vec![newcomer], 1, timelock)
+    .unwrap();
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  assert_eq!(fixture.veto(newcomer), error(ErrorCode::NoPermission));
+  fixture.veto(former).unwrap();
+  assert!(fixture.proposal().vetoed);
+}
+
+#[test]
+fn test_authority_vetoes_at_once() {
+  let mut fixture = Fixture::new();
+  let guardians: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+  fixture.update_guardians(guardians, 2, 50).unwrap();
+  fixture.update_regime(DaoRegime::Democratic);
+  fixture.initialize_proposal(1);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let authority = fixture.authority;
+  fixture.veto(authority).unwrap();
+  let proposal = fixture.proposal();
+  assert_eq!(proposal.vetoes, 0);
+  assert_eq!(proposal.state, ProposalState::Cancelled);
+}
+
+#[test]
+fn test_execution_window_boundaries() {
+  let mut fixture = Fixture::new();
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture
+    .bank
+    .warp_to(fixture.end_date + EXECUTION_WINDOW + 1);
+  assert_eq!(fixture.execute(), error(ErrorCode::ExpiredProposal));
+  fixture.finalize().unwrap();
+  assert_eq!(fixture.proposal().state, ProposalState::Expired);
+}
+
+#[test]
+fn test_failed_transaction_is_rolled_back() {
+  let mut fixture = Fixture::new();
+  let balance = fixture.bank.token_balance(&fixture.voter_token_account());
+
+  fixture.bank.warp_to(fixture.start_date);
+  assert!(fixture.vote_for(balance + 1).is_err());
+  assert_eq!(
+    fixture.bank.token_balance(&fixture.voter_token_account()),
+    balance
+  );
+  assert!(fixture.bank.account(&fixture.receipt(0)).is_none());
+}
+
+#[test]
+fn test_transfer_fee_is_excluded_from_votes() {
+  // 1% of every transfer is withheld by the mint
+  let mut fixture = Fixture::with_transfer_fee(100);
+  let amount = SUPPLY * 3 / 5;
+  let fee = amount / 100;
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(amount).unwrap();
+  let receipt: Receipt = fixture.bank.get(&fixture.receipt(0));
+  assert_eq!(receipt.amount, amount - fee);
+  assert_eq!(fixture.proposal().voting_for_power, (amount - fee) as u128);
+  let treasury = fixture.token_account(&fixture.treasurer());
+  assert_eq!(fixture.bank.token_balance(&treasury), amount - fee);
+
+  // The fee is withheld again when unlocking
+  fixture.bank.warp_to(fixture.end_date + 1);
+  fixture.close().unwrap();
+  assert_eq!(
+    fixture.bank.token_balance(&fixture.voter_token_account()),
+    amount - fee - (amount - fee).div_ceil(100)
+  );
+  assert_eq!(fixture.bank.token_balance(&treasury), 0);
+}
+
+#[test]
+fn test_token_templates() {
+  let mut fixture = Fixture::new();
+  let (authority, master, recipient) = (fixture.authority, fixture.master, fixture.recipient);
+  let sol_transfer = fixture.proposal_instruction;
+  // The master holds governance tokens and the mint authority of a reward mint
+  let mint = fixture.mint;
+  fixture.bank.create_token_account(&mint, &master, 100);
+  let dst = fixture.bank.create_token_account(&mint, &recipient, 0);
+  let reward = fixture.bank.create_mint(&master, 0, 0);
+  let reward_dst = fixture.bank.create_token_account(&reward, &recipient, 0);
+
+  let token_transfer = ProposalInstructionKind::TokenTransfer {
+    mint,
+    dst,
+    amount: 40,
+  };
+  fixture
+    .add_template(authority, token_transfer, Some(mint))
+    .unwrap();
+  let token_transfer = fixture.proposal_instruction;
+  let mint_to = ProposalInstructionKind::MintTo {
+    mint: reward,
+    dst: reward_dst,
+    amount: 7,
+  };
+  fixture
+    .add_template(authority, mint_to, Some(reward))
+    .unwrap();
+  let mint_to = fixture.proposal_instruction;
+  assert_eq!(fixture.proposal().total_instruction, 3);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let balance = fixture.bank.lamports(&recipient);
+  for proposal_instruction in [sol_transfer, token_transfer, mint_to] {
+    fixture.proposal_instruction = proposal_instruction;
+    fixture.execute().unwrap();
+  }
+  assert_eq!(fixture.bank.lamports(&recipient), balance + TRANSFERRED);
+  assert_eq!(fixture.bank.token_balance(&dst), 40);
+  let master_token_account = fixture.token_account(&master);
+  assert_eq!(fixture.bank.token_balance(&master_token_account), 60);
+  assert_eq!(fixture.bank.token_balance(&reward_dst), 7);
+  assert_eq!(fixture.proposal().state, ProposalState::Executed);
+}
+
+#[test]
+fn test_template_validation() {
+  let mut fixture = Fixture::new();
+  let (authority, voter, recipient) = (fixture.authority, fixture.voter, fixture.recipient);
+  let mint = fixture.mint;
+  let other_mint = fixture.bank.create_mint(&authority, 0, 0);
+  let token_transfer = |amount: u64| ProposalInstructionKind::TokenTransfer {
+    mint,
+    dst: recipient,
+    amount,
+  };
+
+  // Only the creator appends instructions
+  let kind = ProposalInstructionKind::SolTransfer {
+    dst: recipient,
+    lamports: 1,
+  };
+  assert_eq!(
+    fixture.add_template(voter, kind, None),
+    error(ErrorCode::NoPermission)
+  );
+  let raw = Instruction {
+    program_id: inter_dao::ID,
+    accounts: inter_dao::accounts::InitializeProposalInstruction {
+      caller: voter,
+      proposal_instruction: Pubkey::new_unique(),
+      invoked_program: system_program::ID,
+      proposal: fixture.proposal,
+      dao: fixture.dao,
+      system_program: system_program::ID,
+      rent: sysvar::rent::ID,
+    }
+    .to_account_metas(None),
+    data: inter_dao::instruction::InitializeProposalInstruction {
+      tx_idx: 0,
+      data: vec![],
+      pubkeys: vec![],
+      is_signers: vec![],
+      is_writables: vec![],
+      is_masters: vec![],
+    }
+    .data(),
+  };
+  assert_eq!(fixture.bank.process(raw), error(ErrorCode::NoPermission));
+
+  // Templates carry a non-zero amount and the matching mint
+  let invalid = error(ErrorCode::InvalidProposalTemplate);
+  let kind = ProposalInstructionKind::SolTransfer {
+    dst: recipient,
+    lamports: 0,
+  };
+  assert_eq!(fixture.add_template(authority, kind, None), invalid);
+  let kind = ProposalInstructionKind::Raw;
+  assert_eq!(fixture.add_template(authority, kind, None), invalid);
+  assert_eq!(
+    fixture.add_template(authority, token_transfer(0), Some(mint)),
+    invalid
+  );
+  assert_eq!(
+    fixture.add_template(authority, token_transfer(1), None),
+    invalid
+  );
+  assert_eq!(
+    fixture.add_template(authority, token_transfer(1), Some(other_mint)),
+    invalid
+  );
+  // Minting requires the master to be the mint authority
+  let kind = ProposalInstructionKind::MintTo {
+    mint,
+    dst: recipient,
+    amount: 1,
+  };
+  assert_eq!(
+    fixture.add_template(authority, kind, Some(mint)),
+    error(ErrorCode::InvalidMintAuthority)
+  );
+
+  // Instructions are frozen once the voting starts
+  fixture.bank.warp_to(fixture.start_date);
+  assert_eq!(
+    fixture.add_template(authority, token_transfer(1), Some(mint)),
+    error(ErrorCode::StartedProposal)
+  );
+  assert_eq!(fixture.proposal().total_instruction, 1);
+}
+
+#[test]
+fn test_content_discussion() {
+  let mut fixture = Fixture::new();
+  let (author, replier) = (fixture.authority, fixture.voter);
+  let proposal = Some(fixture.proposal);
+  let topic = fixture.content(0);
+  let reply = fixture.content(1);
+
+  fixture.post(author, [1; 8], proposal, None).unwrap();
+  fixture
+    .post(replier, [2; 8], proposal, Some(topic))
+    .unwrap();
+  let content: Content = fixture.bank.get(&reply);
+  assert_eq!(content.index, 1);
+  assert_eq!(content.dao, fixture.dao);
+  assert_eq!(content.proposal, fixture.proposal);
+  assert_eq!(content.parent, topic);
+  assert!(content.is_reply());
+  assert_eq!(content.revision, 0);
+  assert_eq!(fixture.dao().content_nonce, 2);
+  // Replies stay within the discussion of their parent
+  assert_eq!(
+    fixture.post(replier, [3; 8], None, Some(topic)),
+    error(ErrorCode::InvalidContentParent)
+  );
+
+  fixture.bank.warp(60);
+  fixture.edit(replier, reply, "").unwrap();
+  let content: Content = fixture.bank.get(&reply);
+  assert_eq!(content.metadata, [3; 32]);
+  assert_eq!(content.updated_at, content.created_at + 60);
+  assert_eq!(content.parent, topic);
+  assert_eq!(content.revision, 1);
+
+  let delete = |authority, content| Instruction {
+    program_id: inter_dao::ID,
+    accounts: inter_dao::accounts::DeleteContent { authority, content }.to_account_metas(None),
+    data: inter_dao::instruction::DeleteContent {}.data(),
+  };
+  assert!(fixture.bank.process(delete(author, reply)).is_err());
+  let balance = fixture.bank.lamports(&replier);
+  let rent = fixture.bank.lamports(&reply);
+  fixture.bank.process(delete(replier, reply)).unwrap();
+  assert!(fixture.bank.account(&reply).is_none());
+  assert_eq!(fixture.bank.lamports(&replier), balance + rent);
+
+  // Deleted addresses are never reused, so replies can't be reattached to new content
+  fixture.bank.process(delete(author, topic)).unwrap();
+  fixture.post(author, [1; 8], proposal, None).unwrap();
+  assert!(fixture.bank.account(&topic).is_none());
+  let content: Content = fixture.bank.get(&fixture.content(2));
+  assert_eq!(content.authority, author);
+}
+
+#[test]
+fn test_content_gated_by_holdings() {
+  let mut fixture = Fixture::new();
+  let dao = fixture.dao;
+  let update_content_threshold = |authority, content_threshold| Instruction {
+    program_id: inter_dao::ID,
+    accounts: inter_dao::accounts::UpdateContentThreshold { authority, dao }.to_account_metas(None),
+    data: inter_dao::instruction::UpdateContentThreshold { content_threshold }.data(),
+  };
+  let (authority, voter) = (fixture.authority, fixture.voter);
+  assert_eq!(
+    fixture
+      .bank
+      .process(update_content_threshold(voter, SUPPLY / 2)),
+    error(ErrorCode::NoPermission)
+  );
+  fixture
+    .bank
+    .process(update_content_threshold(authority, SUPPLY / 2))
+    .unwrap();
+
+  // The authority holds no governance tokens
+  assert_eq!(
+    fixture.post(authority, [1; 8], None, None),
+    error(ErrorCode::InsufficientHoldings)
+  );
+  fixture.post(voter, [1; 8], None, None).unwrap();
+
+  // Holdings are only checked when posting
+  let unlocked_date = fixture.bank.now() + Escrow::MAX_LOCK_DURATION;
+  fixture.lock(SUPPLY * 3 / 5, unlocked_date).unwrap();
+  let content = fixture.content(0);
+  fixture.edit(voter, content, "ipfs://edit").unwrap();
+  assert_eq!(
+    fixture.post(voter, [2; 8], None, None),
+    error(ErrorCode::InsufficientHoldings)
+  );
+}
+
+#[test]
+fn test_metadata_uri_reallocation() {
+  let mut fixture = Fixture::new();
+  let authority = fixture.authority;
+  let content = fixture.content(0);
+  fixture.post(authority, [1; 8], None, None).unwrap();
+  let size = fixture.bank.account(&content).unwrap().data.len();
+  let balance = fixture.bank.lamports(&authority);
+
+  // The authority pays for the grown account and is refunded once it shrinks
+  let uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+  fixture.edit(authority, content, uri).unwrap();
+  let account = fixture.bank.account(&content).unwrap();
+  assert_eq!(account.data.len(), size + uri.len());
+  assert!(fixture.bank.lamports(&authority) < balance);
+  let content_data: Content = fixture.bank.get(&content);
+  assert_eq!(content_data.metadata_uri, uri);
+  assert_eq!(content_data.hash_algorithm, HashAlgorithm::Blake3);
+  fixture.edit(authority, content, "").unwrap();
+  assert_eq!(fixture.bank.account(&content).unwrap().data.len(), size);
+  assert_eq!(fixture.bank.lamports(&authority), balance);
+
+  assert_eq!(
+    fixture.edit(authority, content, "ftp://metadata"),
+    error(ErrorCode::InvalidMetadataUri)
+  );
+  let uri = format!(
+    "https://{}",
+    "a".repeat(inter_dao::constants::MAX_METADATA_URI_SIZE)
+  );
+  assert_eq!(
+    fixture.edit(authority, content, &uri),
+    error(ErrorCode::InvalidMetadataUri)
+  );
+
+  let uri = "https://example.com/dao.json";
+  fixture
+    .bank
+    .process(Instruction {
+      program_id: inter_dao::ID,
+      accounts: inter_dao::accounts::UpdateDaoMetadata {
+        authority,
+        dao: fixture.dao,
+        system_program: system_program::ID,
+      }
+      .to_account_metas(None),
+      data: inter_dao::instruction::UpdateDaoMetadata {
+        metadata: [1; 32],
+        hash_algorithm: HashAlgorithm::Sha256,
+        metadata_uri: uri.to_string(),
+      }
+      .data(),
+    })
+    .unwrap();
+  let dao: Dao = fixture.bank.get(&fixture.dao);
+  assert_eq!(dao.metadata_uri, uri);
+  assert_eq!(
+    fixture.bank.account(&fixture.dao).unwrap().data.len(),
+    Dao::LEN + uri.len()
+  );
+}
+
+#[test]
+fn test_authority_transfer_is_accepted() {
+  let mut fixture = Fixture::new();
+  let (authority, voter) = (fixture.authority, fixture.voter);
+  let new_authority = Pubkey::new_unique();
+  let no_permission = error(ErrorCode::NoPermission);
+
+  assert_eq!(fixture.accept_authority(new_authority), no_permission);
+  assert_eq!(
+    fixture.transfer_authority(voter, new_authority),
+    no_permission
+  );
+  fixture
+    .transfer_authority(authority, new_authority)
+    .unwrap();
+  // The current authority keeps the DAO until the transfer is accepted
+  let dao = fixture.dao();
+  assert_eq!(dao.authority, authority);
+  assert_eq!(dao.pending_authority, new_authority);
+  assert_eq!(fixture.accept_authority(voter), no_permission);
+
+  fixture.accept_authority(new_authority).unwrap();
+  let dao = fixture.dao();
+  assert_eq!(dao.authority, new_authority);
+  assert_eq!(dao.pending_authority, Pubkey::default());
+  assert_eq!(fixture.transfer_authority(authority, voter), no_permission);
+  assert_eq!(fixture.accept_authority(new_authority), no_permission);
+}
+
+#[test]
+fn test_authority_transfer_is_cancelled() {
+  let mut fixture = Fixture::new();
+  let authority = fixture.authority;
+  let new_authority = Pubkey::new_unique();
+
+  assert_eq!(
+    fixture.cancel_authority_transfer(),
+    error(ErrorCode::NoPendingAuthority)
+  );
+  fixture
+    .transfer_authority(authority, new_authority)
+    .unwrap();
+  fixture.cancel_authority_transfer().unwrap();
+  assert_eq!(fixture.dao().pending_authority, Pubkey::default());
+  assert_eq!(
+    fixture.accept_authority(new_authority),
+    error(ErrorCode::NoPermission)
+  );
+  assert_eq!(fixture.dao().authority, authority);
+}
+
+#[test]
+fn test_authority_is_renounced() {
+  let mut fixture = Fixture::new();
+  let (authority, master) = (fixture.authority, fixture.master);
+  let new_authority = Pubkey::new_unique();
+
+  // A dictator cannot leave the DAO without anyone to approve proposals
+  fixture.update_regime(DaoRegime::Dictatorial);
+  assert_eq!(
+    fixture.renounce_authority(),
+    error(ErrorCode::NotAutonomousDao)
+  );
+  fixture.update_regime(DaoRegime::Autonomous);
+
+  fixture
+    .transfer_authority(authority, new_authority)
+    .unwrap();
+  fixture.renounce_authority().unwrap();
+  let dao = fixture.dao();
+  assert_eq!(dao.authority, master);
+  assert_eq!(dao.pending_authority, Pubkey::default());
+  assert_eq!(
+    fixture.accept_authority(new_authority),
+    error(ErrorCode::NoPermission)
+  );
+  assert_eq!(fixture.renounce_authority(), error(ErrorCode::NoPermission));
+}
+
+#[test]
+fn test_metadata_uri_matches_its_hash() {
+  let mut fixture = Fixture::new();
+  let (authority, dao) = (fixture.authority, fixture.dao);
+  let update_metadata = |metadata, hash_algorithm, metadata_uri: &str| Instruction {
+    program_id: inter_dao::ID,
+    accounts: inter_dao::accounts::UpdateDaoMetadata {
+      authority,
+      dao,
+      system_program: system_program::ID,
+    }
+    .to_account_metas(None),
+    data: inter_dao::instruction::UpdateDaoMetadata {
+      metadata,
+      hash_algorithm,
+      metadata_uri: metadata_uri.to_string(),
+    }
+    .data(),
+  };
+  let invalid = error(ErrorCode::InvalidMetadataUri);
+
+  // Content-addressed URIs are hashed the way they are addressed
+  for (hash_algorithm, uri) in [
+    (HashAlgorithm::Keccak256, "ipfs://bafy"),
+    (HashAlgorithm::Blake3, "ar://tx"),
+    (HashAlgorithm::Keccak256, "ar://tx"),
+  ] {
+    assert_eq!(
+      fixture
+        .bank
+        .process(update_metadata([1; 32], hash_algorithm, uri)),
+      invalid
+    );
+  }
+  // Mutable hosts are only trusted against a hash
+  assert_eq!(
+    fixture.bank.process(update_metadata(
+      [0; 32],
+      HashAlgorithm::Sha256,
+      "https://example.com/dao.json"
+    )),
+    invalid
+  );
+  for (metadata, hash_algorithm, uri) in [
+    (
+      [1; 32],
+      HashAlgorithm::Keccak256,
+      "https://example.com/dao.json",
+    ),
+    ([1; 32], HashAlgorithm::Blake3, "ipfs://bafy"),
+    ([1; 32], HashAlgorithm::Sha256, "ar://tx"),
+    ([0; 32], HashAlgorithm::Keccak256, ""),
+  ] {
+    fixture
+      .bank
+      .process(update_metadata(metadata, hash_algorithm, uri))
+      .unwrap();
+  }
+}
+
+#[test]
+fn test_master_updates_the_dao_through_a_proposal() {
+  let mut fixture = Fixture::new();
+  let (authority, dao, master) = (fixture.authority, fixture.dao, fixture.master);
+  let uri = "https://example.com/dao.json";
+  let update_metadata = Instruction {
+    program_id: inter_dao::ID,
+    accounts: inter_dao::accounts::UpdateDaoMetadata {
+      authority: master,
+      dao,
+      system_program: system_program::ID,
+    }
+    .to_account_metas(None),
+    data: inter_dao::instruction::UpdateDaoMetadata {
+      metadata: [1; 32],
+      hash_algorithm: HashAlgorithm::Sha256,
+      metadata_uri: uri.to_string(),
+    }
+    .data(),
+  };
+  fixture.replace(&update_metadata).unwrap();
+  let proposal_instruction: ProposalInstruction = fixture.bank.get(&fixture.proposal_instruction);
+  assert!(proposal_instruction.accounts[0].is_master);
+
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  fixture.bank.warp_to(fixture.end_date + 1);
+  let (master_balance, dao_balance) = (fixture.bank.lamports(&master), fixture.bank.lamports(&dao));
+  let authority_balance = fixture.bank.lamports(&authority);
+  fixture.execute().unwrap();
+
+  // The master signs the self-invocation and pays for the grown DAO account
+  let dao_data: Dao = fixture.bank.get(&dao);
+  assert_eq!(dao_data.metadata, [1; 32]);
+  assert_eq!(dao_data.metadata_uri, uri);
+  assert_eq!(
+    fixture.bank.account(&dao).unwrap().data.len(),
+    Dao::LEN + uri.len()
+  );
+  let rent = fixture.bank.lamports(&dao) - dao_balance;
+  assert!(rent > 0);
+  assert_eq!(fixture.bank.lamports(&master), master_balance - rent);
+  assert_eq!(fixture.bank.lamports(&authority), authority_balance);
+  assert_eq!(fixture.proposal().state, ProposalState::Executed);
+}
+
+///
+/// Record the log fixtures of the client event decoder:
+/// cargo test -p interDAO --test governance -- --ignored record_event_fixtures
+///
+#[test]
+#[ignore]
+fn record_event_fixtures() {
+  let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+  let write = |name: &str, logs: Vec<String>| {
+    std::fs::write(format!("{}/{}.log", dir, name), logs.join("\n") + "\n").unwrap();
+  };
+  let mut fixture = Fixture::new();
+  write("initialize_proposal", fixture.bank.logs());
+  fixture.bank.warp_to(fixture.start_date);
+  fixture.vote_for(SUPPLY * 3 / 5).unwrap();
+  write("vote_for", fixture.bank.logs());
+  // The voter has nothing left to lock
+  assert!(fixture.vote_for(1).is_err());
+  write("vote_for_failed", fixture.bank.logs());
+  fixture.bank.warp_to(fixture.end_date + 1);
+  fixture.status();
+  write("get_proposal_status", fixture.bank.logs());
+  fixture.execute().unwrap();
+  write("execute_proposal_instruction", fixture.bank.logs());
+  fixture.close().unwrap();
+  write("close", fixture.bank.logs());
+  fixture.update_timelock(0);
+  write("update_dao_guardians", fixture.bank.logs());
+  let authority = fixture.authority;
+  fixture.post(authority, [1; 8], None, None).unwrap();
+  let content = fixture.content(0);
+  fixture.edit(authority, content, "ipfs://edit").unwrap();
+  write("edit_content", fixture.bank.logs());
+}
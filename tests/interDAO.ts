@@ -3,11 +3,11 @@ import {
   setProvider,
   workspace,
   utils,
-  Spl,
   BN,
   AnchorProvider,
   Program,
-} from '@project-serum/anchor'
+} from '@coral-xyz/anchor'
+import { splTokenProgram } from '@coral-xyz/spl-token'
 import { InterDao } from '../target/types/inter_dao'
 import { initializeAccount, initializeMint } from './pretest'
 import * as soproxABI from 'soprox-abi'
@@ -49,7 +49,7 @@ describe('interDAO', () => {
   setProvider(provider)
 
   const program = workspace.InterDao as Program<InterDao>
-  const spl = Spl.token()
+  const spl = splTokenProgram({ provider })
   const mint = new web3.Keypair()
   let tokenAccount: web3.PublicKey
   const dao = new web3.Keypair()
@@ -58,6 +58,7 @@ describe('interDAO', () => {
   let proposal: web3.PublicKey
   let voteForReceipt: web3.PublicKey
   let voteAgainstReceipt: web3.PublicKey
+  let voterRecord: web3.PublicKey
   let treasurer: web3.PublicKey
   let treasury: web3.PublicKey
   const currentTime = Math.floor(Number(new Date()) / 1000)
@@ -82,8 +83,8 @@ describe('interDAO', () => {
     await spl.rpc.mintTo(new BN(1_000_000_000_000), {
       accounts: {
         mint: mint.publicKey,
-        to: tokenAccount,
-        authority: provider.wallet.publicKey,
+        account: tokenAccount,
+        owner: provider.wallet.publicKey,
       },
     })
     // Derive master account
@@ -101,8 +102,8 @@ describe('interDAO', () => {
     await spl.rpc.mintTo(new BN(1_000_000_000_000), {
       accounts: {
         mint: mint.publicKey,
-        to: daoTreasury,
-        authority: provider.wallet.publicKey,
+        account: daoTreasury,
+        owner: provider.wallet.publicKey,
       },
     })
     // Derive proposal account
@@ -145,6 +146,16 @@ describe('interDAO', () => {
         program.programId,
       )
     voteAgainstReceipt = voteAgainstReceiptPublicKey
+    // Receipts are indexed by the voter record in the voting order
+    const [voterRecordPublicKey] = await web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from('voter_record'),
+        proposal.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+      ],
+      program.programId,
+    )
+    voterRecord = voterRecordPublicKey
   })
 
   it('add listeners', async () => {
@@ -163,8 +174,15 @@ describe('interDAO', () => {
       DaoRegimes.Autonomous,
       new BN(1),
       PRIMARY_DUMMY_METADATA,
+      { sha256: {} }, // hash algorithm
+      '', // metadata uri
       false,
       true,
+      new BN(10), // min voting duration
+      new BN(7776000), // max voting duration
+      new BN(0), // min notice period
+      new BN(0), // execution window
+      new BN(600), // retry window
       {
         accounts: {
           dao: dao.publicKey,
@@ -190,6 +208,9 @@ describe('interDAO', () => {
         new BN(currentTime + 10),
         new BN(currentTime + 20),
         PRIMARY_DUMMY_METADATA,
+        { sha256: {} }, // hash algorithm
+        '', // metadata uri
+        false, // independent instructions
         new BN(10 ** 6), // tax
         new BN(10 ** 6), // revenue
       )
@@ -260,7 +281,7 @@ describe('interDAO', () => {
       await program.account.proposal.fetch(proposal)
     console.log('Prev Voting-For Power', prevVotingForPower.toString())
 
-    await program.rpc.voteFor(new BN(0), new BN(10), new BN(0), new BN(0), {
+    await program.rpc.voteFor(new BN(10), new BN(0), new BN(0), {
       accounts: {
         authority: provider.wallet.publicKey,
         src: tokenAccount,
@@ -270,6 +291,7 @@ describe('interDAO', () => {
         proposal,
         dao: dao.publicKey,
         receipt: voteForReceipt,
+        voterRecord,
         tokenProgram: utils.token.TOKEN_PROGRAM_ID,
         associatedTokenProgram: utils.token.ASSOCIATED_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
@@ -289,7 +311,7 @@ describe('interDAO', () => {
       await program.account.proposal.fetch(proposal)
     console.log('Prev Voting-Against Power', prevVotingAgainstPower.toString())
 
-    await program.rpc.voteAgainst(new BN(1), new BN(1), new BN(0), new BN(0), {
+    await program.rpc.voteAgainst(new BN(1), new BN(0), new BN(0), {
       accounts: {
         authority: provider.wallet.publicKey,
         src: tokenAccount,
@@ -299,6 +321,7 @@ describe('interDAO', () => {
         proposal,
         dao: dao.publicKey,
         receipt: voteAgainstReceipt,
+        voterRecord,
         tokenProgram: utils.token.TOKEN_PROGRAM_ID,
         associatedTokenProgram: utils.token.ASSOCIATED_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
@@ -316,7 +339,7 @@ describe('interDAO', () => {
   it('execute the proposal instruction', async () => {
     await asyncWait(20000) // Wait for a minute
 
-    const { amount: prevAmount } = await spl.account.token.fetch(daoTreasury)
+    const { amount: prevAmount } = await spl.account.account.fetch(daoTreasury)
     console.log('Prev Amount', prevAmount.toString())
 
     const remainingAccounts = [
@@ -339,7 +362,7 @@ describe('interDAO', () => {
         .remainingAccounts(remainingAccounts)
         .rpc()
 
-      const { amount: nextAmount } = await spl.account.token.fetch(daoTreasury)
+      const { amount: nextAmount } = await spl.account.account.fetch(daoTreasury)
       const { totalExecuted } = await program.account.proposal.fetch(proposal)
       console.log('totalExecuted', totalExecuted.toString())
       console.log(' Next Amount', nextAmount.toString())
@@ -427,14 +450,24 @@ describe('interDAO', () => {
   })
 
   it('update DAO metadata', async () => {
-    await program.rpc.updateDaoMetadata(SECONDARY_DUMMY_METADATA, {
-      accounts: {
-        authority: provider.wallet.publicKey,
-        dao: dao.publicKey,
+    const uri = 'ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi'
+    await program.rpc.updateDaoMetadata(
+      SECONDARY_DUMMY_METADATA,
+      { sha256: {} },
+      uri,
+      {
+        accounts: {
+          authority: provider.wallet.publicKey,
+          dao: dao.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        },
       },
-    })
-    const { metadata } = await program.account.dao.fetch(dao.publicKey)
+    )
+    const { metadata, metadataUri } = await program.account.dao.fetch(
+      dao.publicKey,
+    )
     expect(metadata).deep.equal(SECONDARY_DUMMY_METADATA)
+    expect(metadataUri).to.equal(uri)
   })
 
   it('transfer authority', async () => {
@@ -446,8 +479,11 @@ describe('interDAO', () => {
         dao: dao.publicKey,
       },
     })
-    const { authority } = await program.account.dao.fetch(dao.publicKey)
-    expect(authority.equals(newAuthority)).true
+    const { authority, pendingAuthority } = await program.account.dao.fetch(
+      dao.publicKey,
+    )
+    expect(authority.equals(provider.wallet.publicKey)).true
+    expect(pendingAuthority.equals(newAuthority)).true
   })
 
   it('remove listeners', async () => {
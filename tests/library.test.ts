@@ -3,11 +3,11 @@ import {
   setProvider,
   workspace,
   utils,
-  Spl,
   BN,
   AnchorProvider,
   Program,
-} from '@project-serum/anchor'
+} from '@coral-xyz/anchor'
+import { splTokenProgram } from '@coral-xyz/spl-token'
 
 import { initializeAccount, initializeMint } from './pretest'
 import * as soproxABI from 'soprox-abi'
@@ -61,7 +61,7 @@ describe('Library Test', () => {
     program.programId.toBase58(),
   )
 
-  const spl = Spl.token()
+  const spl = splTokenProgram({ provider })
   const mint = new web3.Keypair()
   let tokenAccount: web3.PublicKey
   const dao = new web3.Keypair()
@@ -70,6 +70,7 @@ describe('Library Test', () => {
   let proposal: web3.PublicKey
   let voteForReceipt: web3.PublicKey
   let voteAgainstReceipt: web3.PublicKey
+  let voterRecord: web3.PublicKey
   let treasurer: web3.PublicKey
   let treasury: web3.PublicKey
   const currentTime = Math.floor(Number(new Date()) / 1000)
@@ -94,8 +95,8 @@ describe('Library Test', () => {
     await spl.rpc.mintTo(new BN(1_000_000_000_000), {
       accounts: {
         mint: mint.publicKey,
-        to: tokenAccount,
-        authority: provider.wallet.publicKey,
+        account: tokenAccount,
+        owner: provider.wallet.publicKey,
       },
     })
     // Derive master account
@@ -113,8 +114,8 @@ describe('Library Test', () => {
     await spl.rpc.mintTo(new BN(1_000_000_000_000), {
       accounts: {
         mint: mint.publicKey,
-        to: daoTreasury,
-        authority: provider.wallet.publicKey,
+        account: daoTreasury,
+        owner: provider.wallet.publicKey,
       },
     })
     // Derive proposal account
@@ -157,6 +158,16 @@ describe('Library Test', () => {
         program.programId,
       )
     voteAgainstReceipt = voteAgainstReceiptPublicKey
+    // Receipts are indexed by the voter record in the voting order
+    const [voterRecordPublicKey] = await web3.PublicKey.findProgramAddress(
+      [
+        Buffer.from('voter_record'),
+        proposal.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+      ],
+      program.programId,
+    )
+    voterRecord = voterRecordPublicKey
   })
 
   it('add listeners', async () => {
@@ -175,8 +186,15 @@ describe('Library Test', () => {
       DaoRegimes.Autonomous,
       new BN(1),
       PRIMARY_DUMMY_METADATA,
+      { sha256: {} }, // hash algorithm
+      '', // metadata uri
       false,
       true,
+      new BN(10), // min voting duration
+      new BN(7776000), // max voting duration
+      new BN(0), // min notice period
+      new BN(0), // execution window
+      new BN(600), // retry window
       {
         accounts: {
           dao: dao.publicKey,
@@ -249,7 +267,7 @@ describe('Library Test', () => {
       await program.account.proposal.fetch(proposal)
     console.log('Prev Voting-For Power', prevVotingForPower.toString())
 
-    await program.rpc.voteFor(new BN(0), new BN(10), new BN(0), new BN(0), {
+    await program.rpc.voteFor(new BN(10), new BN(0), new BN(0), {
       accounts: {
         authority: provider.wallet.publicKey,
         src: tokenAccount,
@@ -259,6 +277,7 @@ describe('Library Test', () => {
         proposal,
         dao: dao.publicKey,
         receipt: voteForReceipt,
+        voterRecord,
         tokenProgram: utils.token.TOKEN_PROGRAM_ID,
         associatedTokenProgram: utils.token.ASSOCIATED_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
@@ -278,7 +297,7 @@ describe('Library Test', () => {
       await program.account.proposal.fetch(proposal)
     console.log('Prev Voting-Against Power', prevVotingAgainstPower.toString())
 
-    await program.rpc.voteAgainst(new BN(1), new BN(1), new BN(0), new BN(0), {
+    await program.rpc.voteAgainst(new BN(1), new BN(0), new BN(0), {
       accounts: {
         authority: provider.wallet.publicKey,
         src: tokenAccount,
@@ -288,6 +307,7 @@ describe('Library Test', () => {
         proposal,
         dao: dao.publicKey,
         receipt: voteAgainstReceipt,
+        voterRecord,
         tokenProgram: utils.token.TOKEN_PROGRAM_ID,
         associatedTokenProgram: utils.token.ASSOCIATED_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
@@ -305,10 +325,10 @@ describe('Library Test', () => {
   it('execute the proposal instruction', async () => {
     await asyncWait(20000) // Wait for a minute
 
-    const { amount: prevAmount } = await spl.account.token.fetch(daoTreasury)
+    const { amount: prevAmount } = await spl.account.account.fetch(daoTreasury)
     await interDaoProgram.executeProposal({ proposal: proposal.toBase58() })
 
-    const { amount: nextAmount } = await spl.account.token.fetch(daoTreasury)
+    const { amount: nextAmount } = await spl.account.account.fetch(daoTreasury)
     const { totalExecuted } = await program.account.proposal.fetch(proposal)
     console.log('totalExecuted', totalExecuted.toString())
     console.log(' Next Amount', nextAmount.toString())
@@ -395,14 +415,24 @@ describe('Library Test', () => {
   })
 
   it('update DAO metadata', async () => {
-    await program.rpc.updateDaoMetadata(SECONDARY_DUMMY_METADATA, {
-      accounts: {
-        authority: provider.wallet.publicKey,
-        dao: dao.publicKey,
+    const uri = 'ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi'
+    await program.rpc.updateDaoMetadata(
+      SECONDARY_DUMMY_METADATA,
+      { sha256: {} },
+      uri,
+      {
+        accounts: {
+          authority: provider.wallet.publicKey,
+          dao: dao.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        },
       },
-    })
-    const { metadata } = await program.account.dao.fetch(dao.publicKey)
+    )
+    const { metadata, metadataUri } = await program.account.dao.fetch(
+      dao.publicKey,
+    )
     expect(metadata).deep.equal(SECONDARY_DUMMY_METADATA)
+    expect(metadataUri).to.equal(uri)
   })
 
   it('transfer authority', async () => {
@@ -414,8 +444,11 @@ describe('Library Test', () => {
         dao: dao.publicKey,
       },
     })
-    const { authority } = await program.account.dao.fetch(dao.publicKey)
-    expect(authority.equals(newAuthority)).true
+    const { authority, pendingAuthority } = await program.account.dao.fetch(
+      dao.publicKey,
+    )
+    expect(authority.equals(provider.wallet.publicKey)).true
+    expect(pendingAuthority.equals(newAuthority)).true
   })
 
   it('remove listeners', async () => {
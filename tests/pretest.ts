@@ -1,13 +1,7 @@
 // @ts-ignore
 import * as soproxABI from 'soprox-abi'
-import {
-  web3,
-  Spl,
-  utils,
-  AnchorProvider,
-  Program,
-  SplToken,
-} from '@project-serum/anchor'
+import { web3, utils, AnchorProvider, Program } from '@coral-xyz/anchor'
+import { SplToken } from '@coral-xyz/spl-token'
 
 const TOKEN_METADATA_PROGRAM_ID = new web3.PublicKey(
   'metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s',